@@ -0,0 +1,41 @@
+CREATE TABLE "roles" (
+    "id" UUID PRIMARY KEY,
+    "name" TEXT UNIQUE NOT NULL,
+    "created_at" TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE TABLE "permissions" (
+    "id" UUID PRIMARY KEY,
+    "name" TEXT UNIQUE NOT NULL
+);
+
+CREATE TABLE "role_permissions" (
+    "role_id" UUID NOT NULL REFERENCES "roles" ("id") ON DELETE CASCADE,
+    "permission_id" UUID NOT NULL REFERENCES "permissions" ("id") ON DELETE CASCADE,
+    PRIMARY KEY ("role_id", "permission_id")
+);
+
+CREATE TABLE "user_roles" (
+    "user_id" UUID NOT NULL REFERENCES "users" ("id") ON DELETE CASCADE,
+    "role_id" UUID NOT NULL REFERENCES "roles" ("id") ON DELETE CASCADE,
+    "created_at" TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY ("user_id", "role_id")
+);
+
+-- The permission names must match the constants declared in
+-- features/role/domain/permissions.rs
+
+INSERT INTO "permissions" ("id", "name") VALUES
+    (gen_random_uuid(), 'users:update'),
+    (gen_random_uuid(), 'users:delete'),
+    (gen_random_uuid(), 'roles:manage');
+
+INSERT INTO "roles" ("id", "name") VALUES (gen_random_uuid(), 'admin');
+
+INSERT INTO "role_permissions" ("role_id", "permission_id")
+SELECT r."id", p."id" FROM "roles" r CROSS JOIN "permissions" p
+WHERE r."name" = 'admin';
+
+-- The first admin has to be granted by hand, e.g:
+-- INSERT INTO user_roles (user_id, role_id, created_at)
+-- SELECT '<user id>', id, now() FROM roles WHERE name = 'admin';
@@ -0,0 +1,11 @@
+CREATE TABLE "email_verification_tokens" (
+    "id" UUID PRIMARY KEY,
+    "user_id" UUID NOT NULL REFERENCES "users" ("id") ON DELETE CASCADE,
+    "token_hash" TEXT UNIQUE NOT NULL,
+    "expires_at" TIMESTAMPTZ NOT NULL,
+    "used_at" TIMESTAMPTZ,
+    "created_at" TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX "email_verification_tokens_user_id_idx"
+ON "email_verification_tokens" ("user_id");
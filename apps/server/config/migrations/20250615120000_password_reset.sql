@@ -0,0 +1,11 @@
+CREATE TABLE "password_reset_tokens" (
+    "id" UUID PRIMARY KEY,
+    "user_id" UUID NOT NULL REFERENCES "users" ("id") ON DELETE CASCADE,
+    "token_hash" TEXT UNIQUE NOT NULL,
+    "expires_at" TIMESTAMPTZ NOT NULL,
+    "used_at" TIMESTAMPTZ,
+    "created_at" TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX "password_reset_tokens_user_id_idx"
+ON "password_reset_tokens" ("user_id");
@@ -0,0 +1,13 @@
+-- Usernames and emails are unique regardless of letter case. The
+-- constraints are the source of truth, the repository maps their
+-- violations to `UsernameAlreadyExists` / `EmailAlreadyExists`.
+
+-- Fails if the table already contains case-only duplicates, those
+-- have to be resolved by hand before running it.
+
+CREATE EXTENSION IF NOT EXISTS citext;
+
+ALTER TABLE "users" ALTER COLUMN "email" TYPE CITEXT;
+
+CREATE UNIQUE INDEX "users_username_lower_key"
+ON "users" (lower("username"));
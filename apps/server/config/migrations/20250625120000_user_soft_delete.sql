@@ -0,0 +1,17 @@
+-- Deleted users are kept with `deleted_at` set until they are restored,
+-- purged by an admin or removed by the retention job. Soft-deleted rows
+-- keep their username/email reserved so they can always be restored.
+
+ALTER TABLE "users" ADD COLUMN "deleted_at" TIMESTAMPTZ;
+
+CREATE INDEX "users_deleted_at_idx"
+ON "users" ("deleted_at") WHERE "deleted_at" IS NOT NULL;
+
+-- Must match the constant in features/role/domain/permissions.rs
+
+INSERT INTO "permissions" ("id", "name") VALUES
+    (gen_random_uuid(), 'users:purge');
+
+INSERT INTO "role_permissions" ("role_id", "permission_id")
+SELECT r."id", p."id" FROM "roles" r CROSS JOIN "permissions" p
+WHERE r."name" = 'admin' AND p."name" = 'users:purge';
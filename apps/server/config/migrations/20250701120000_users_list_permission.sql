@@ -0,0 +1,9 @@
+-- The user listing exposes every email, it is restricted to the admins.
+-- Must match the constant in features/role/domain/permissions.rs
+
+INSERT INTO "permissions" ("id", "name") VALUES
+    (gen_random_uuid(), 'users:list');
+
+INSERT INTO "role_permissions" ("role_id", "permission_id")
+SELECT r."id", p."id" FROM "roles" r CROSS JOIN "permissions" p
+WHERE r."name" = 'admin' AND p."name" = 'users:list';
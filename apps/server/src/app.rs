@@ -1,47 +1,96 @@
-use std::sync::Arc;
+use std::{future::IntoFuture, sync::Arc, time::Duration};
 
-use axum::Router;
-use axum_responses::{http::HttpResponse, response};
-use tokio::net::TcpListener;
+use axum::{middleware, Router};
+use shaku::HasComponent;
+use tokio::{net::TcpListener, sync::Notify};
 use tower_http::cors::CorsLayer;
 
-use crate::features::user::infrastructure::user_router;
+use crate::features::auth::infrastructure::auth_router;
+use crate::features::role::infrastructure::role_router;
+use crate::features::user::infrastructure::{spawn_retention_job, user_router};
 
+use crate::shared::application::{
+    shutdown_hook, HealthChecks, Mailer, ShutdownHooks,
+};
 use crate::shared::infrastructure::{
-    logger::HttpLogger,
+    configured_mailer, i18n,
+    logger::{propagate_trace_context, HttpLogger},
+    metrics::{router as metrics_router, track_requests},
+    openapi::router as openapi_router,
+    probes::router as health_router,
+    problem::problem_details,
+    request_id::request_id,
+    run_shutdown_hooks, shutdown_signal, DatabaseConnection, DatabaseHealthCheck,
     PostgresDatabase, {AppModule, AppState},
 };
 
-use crate::shared::constants::{
-    check_env_vars, ALLOWED_HTTP_HEADERS, ALLOWED_HTTP_METHODS,
-};
+use crate::shared::settings::{AppSettings, Settings};
 
 pub struct Application {
     router: Router,
+    state: AppState,
+    address: String,
+    shutdown_timeout: Duration,
 }
 
 impl Application {
     pub async fn new() -> Self {
-        check_env_vars();
+        let settings = Settings::load()
+            .unwrap_or_else(|e| panic!("Invalid configuration:\n{e}"));
+
+        i18n::load_catalogs();
+
+        let http_logger = HttpLogger::new(&settings);
+        let di_state = Application::set_up_di(&settings).await;
+
+        let provider = http_logger.provider.clone();
+        let hooks: &dyn ShutdownHooks = di_state.module.resolve_ref();
+        hooks.register(
+            "tracer provider",
+            shutdown_hook(move || async move {
+                let _ =
+                    tokio::task::spawn_blocking(move || provider.shutdown()).await;
+            }),
+        );
 
-        let di_state = Application::set_up_di().await;
+        spawn_retention_job(di_state.clone());
 
-        let http_logger = HttpLogger::new();
         let cors_layer = CorsLayer::new()
-            .allow_methods(ALLOWED_HTTP_METHODS.to_owned())
-            .allow_headers(ALLOWED_HTTP_HEADERS.to_owned());
+            .allow_methods(settings.cors.methods())
+            .allow_headers(settings.cors.headers());
 
-        let app_router = Router::new()
-            .merge(user_router(di_state))
-            .route("/health", axum::routing::get(Application::health_check))
+        let app_router = Application::routes(di_state.clone())
             .layer(cors_layer)
-            .layer(http_logger.layer);
+            .layer(middleware::from_fn(propagate_trace_context))
+            .layer(http_logger.layer)
+            .layer(middleware::from_fn(request_id));
+
+        Application {
+            router: app_router,
+            state: di_state,
+            address: settings.server.address(),
+            shutdown_timeout: settings.server.shutdown_timeout(),
+        }
+    }
 
-        Application { router: app_router }
+    // Routes of every feature with the layers that shape their responses,
+    // also used by the tests to send requests without a server (see
+    // `shared::infrastructure::testing`).
+
+    pub fn routes(state: AppState) -> Router {
+        Router::new()
+            .merge(auth_router(state.clone()))
+            .merge(role_router(state.clone()))
+            .merge(user_router(state.clone()))
+            .merge(health_router(state.clone()))
+            .merge(metrics_router(state))
+            .merge(openapi_router())
+            .layer(middleware::from_fn(problem_details))
+            .layer(middleware::from_fn(track_requests))
     }
 
-    pub async fn set_up_di() -> AppState {
-        let db_connection = PostgresDatabase::new()
+    pub async fn set_up_di(settings: &Settings) -> AppState {
+        let db_connection = PostgresDatabase::new(&settings.database)
             .await
             .expect("Failed to create database connection");
 
@@ -50,29 +99,76 @@ impl Application {
             .await
             .expect("Failed to run database migrations");
 
+        let mailer = configured_mailer(&settings.mail)
+            .expect("Failed to configure the mailer");
+
         let di_module = AppModule::builder()
+            .with_component_parameters::<AppSettings>(settings.clone().into())
             .with_component_parameters::<PostgresDatabase>(db_connection.into())
+            .with_component_override::<dyn Mailer>(mailer)
             .build();
 
+        // The pool is closed last, after every other hook released its
+        // resources (hooks run in reverse registration order)
+
+        let database: &dyn DatabaseConnection = di_module.resolve_ref();
+        let pool = database.get_pool().clone();
+
+        let checks: &dyn HealthChecks = di_module.resolve_ref();
+        checks.register(Arc::new(DatabaseHealthCheck::new(pool.clone())));
+
+        let hooks: &dyn ShutdownHooks = di_module.resolve_ref();
+        hooks.register(
+            "database pool",
+            shutdown_hook(move || async move { pool.close().await }),
+        );
+
         AppState {
             module: Arc::new(di_module),
         }
     }
 
     pub async fn run(&self) -> Result<(), Box<dyn std::error::Error>> {
-        let listener = TcpListener::bind("0.0.0.0:8000").await?;
+        let listener = TcpListener::bind(&self.address).await?;
 
-        println!("Server listening on port 8000");
+        tracing::info!("Server listening on {}", self.address);
 
-        axum::serve(listener, self.router.clone()).await?;
+        let drain = Arc::new(Notify::new());
+        let drain_signal = drain.clone();
 
-        Ok(())
-    }
+        let server = axum::serve(listener, self.router.clone())
+            .with_graceful_shutdown(async move { drain_signal.notified().await });
+        let mut server = tokio::spawn(server.into_future());
+
+        tokio::select! {
+            result = &mut server => return Ok(result??),
+            signal = shutdown_signal() => {
+                tracing::info!("Received {signal}, shutting down");
+            }
+        }
+
+        // New connections are refused from here, the ones in flight get
+        // up to `server.shutdown_timeout` seconds to complete
+
+        tracing::info!(
+            "Draining in-flight requests (timeout {:?})",
+            self.shutdown_timeout
+        );
+        drain.notify_one();
+
+        match tokio::time::timeout(self.shutdown_timeout, &mut server).await {
+            Ok(_) => tracing::info!("All in-flight requests completed"),
+            Err(_) => {
+                tracing::warn!("Drain timeout reached, dropping open connections");
+                server.abort();
+            }
+        }
 
-    pub async fn health_check() -> HttpResponse {
-        let time = chrono::Utc::now();
-        let status = "running";
+        let hooks: &dyn ShutdownHooks = self.state.module.resolve_ref();
+        run_shutdown_hooks(hooks, self.shutdown_timeout).await;
 
-        response!(200, { "status": status, "time": time.to_string() })
+        tracing::info!("Shutdown complete");
+
+        Ok(())
     }
 }
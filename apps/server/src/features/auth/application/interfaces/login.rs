@@ -0,0 +1,32 @@
+// This module defines the LoginCase Trait/Interface and its
+// corresponding Input DTO LoginInput.
+
+// |----------------------------------------------------------------|
+// |                 Input entities between layers                  |
+// |----------------------------------------------------------------|
+// |   Auth Infrastructure Layer (LoginDto)    |     Controller     |
+// |------------------------------------------ |--------------------|
+// |   Auth Application Layer (LoginInput)     |      Use Case      |
+// |------------------------------------------ |--------------------|
+// |      Auth Domain Layer (AuthTokens)       |     Repository     |
+// |----------------------------------------------------------------|
+
+use async_trait::async_trait;
+use shaku::Interface;
+
+use crate::features::auth::domain::{AuthError, AuthTokens};
+
+// Credentials required to open a new session.
+
+pub struct LoginInput {
+    pub email: String,
+    pub password: String,
+}
+
+// Use case definition for authenticating a user with its credentials.
+// implementation in: /features/auth/application/usecases/login.rs
+
+#[async_trait]
+pub trait LoginCase: Interface {
+    async fn execute(&self, input: LoginInput) -> Result<AuthTokens, AuthError>;
+}
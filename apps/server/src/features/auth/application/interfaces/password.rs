@@ -0,0 +1,32 @@
+// This module defines the ForgotPasswordCase and ResetPasswordCase
+// Traits/Interfaces and the ResetPasswordInput DTO.
+
+use async_trait::async_trait;
+use shaku::Interface;
+
+use crate::features::auth::domain::AuthError;
+
+// Use case definition for requesting a password reset link. It succeeds
+// silently for unknown emails, so it can't be used to find out which
+// emails are registered.
+// implementation in: /features/auth/application/usecases/password.rs
+
+#[async_trait]
+pub trait ForgotPasswordCase: Interface {
+    async fn execute(&self, email: String) -> Result<(), AuthError>;
+}
+
+// The token received by email and the new (already validated) password.
+
+pub struct ResetPasswordInput {
+    pub token: String,
+    pub password: String,
+}
+
+// Use case definition for setting a new password with a reset token.
+// Every open session of the user is revoked on success.
+
+#[async_trait]
+pub trait ResetPasswordCase: Interface {
+    async fn execute(&self, input: ResetPasswordInput) -> Result<(), AuthError>;
+}
@@ -0,0 +1,15 @@
+// This module defines the RefreshTokenCase Trait/Interface.
+
+use async_trait::async_trait;
+use shaku::Interface;
+
+use crate::features::auth::domain::{AuthError, AuthTokens};
+
+// Use case definition for exchanging a refresh token for a new token pair.
+// The received refresh token is revoked (rotated) on success.
+// implementation in: /features/auth/application/usecases/refresh.rs
+
+#[async_trait]
+pub trait RefreshTokenCase: Interface {
+    async fn execute(&self, refresh_token: String) -> Result<AuthTokens, AuthError>;
+}
@@ -0,0 +1,27 @@
+pub mod interfaces {
+    mod login;
+    mod password;
+    mod refresh;
+
+    pub use login::*;
+    pub use password::*;
+    pub use refresh::*;
+}
+
+pub mod services {
+    mod session;
+    mod tokens;
+
+    pub use session::*;
+    pub use tokens::*;
+}
+
+pub mod usecases {
+    mod login;
+    mod password;
+    mod refresh;
+
+    pub use login::*;
+    pub use password::*;
+    pub use refresh::*;
+}
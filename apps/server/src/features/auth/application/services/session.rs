@@ -0,0 +1,81 @@
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use shaku::{Component, Interface};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::tokens::{TokenKind, TokenService};
+use crate::features::{
+    auth::domain::{AuthError, AuthTokens, RefreshToken, RefreshTokenRepository},
+    role::domain::RoleRepository,
+};
+
+// Issues a new access/refresh token pair for a user and persists the
+// refresh token, shared by the login and refresh use cases.
+
+#[async_trait]
+pub trait SessionService: Interface {
+    async fn open(&self, user_id: Uuid) -> Result<AuthTokens, AuthError>;
+}
+
+#[derive(Component)]
+#[shaku(interface = SessionService)]
+pub struct SessionServiceImpl {
+    #[shaku(inject)]
+    tokens: Arc<dyn TokenService>,
+    #[shaku(inject)]
+    repository: Arc<dyn RefreshTokenRepository>,
+    #[shaku(inject)]
+    roles: Arc<dyn RoleRepository>,
+}
+
+#[async_trait]
+impl SessionService for SessionServiceImpl {
+    async fn open(&self, user_id: Uuid) -> Result<AuthTokens, AuthError> {
+        let access_ttl = self.tokens.lifetime(TokenKind::Access);
+        let refresh_ttl = self.tokens.lifetime(TokenKind::Refresh);
+
+        let now = Utc::now();
+        let refresh = self
+            .repository
+            .create(RefreshToken {
+                id: Uuid::new_v4(),
+                user_id,
+                revoked: false,
+                expires_at: now + Duration::seconds(refresh_ttl),
+                created_at: now,
+            })
+            .await?;
+
+        // Roles and permissions are embedded in the access token so the
+        // `AuthUser` extractor can authorize requests without hitting the
+        // database. Changes to the user roles apply on the next refresh
+
+        let roles = self.roles.find_by_user(user_id).await?;
+
+        let mut access_claims =
+            self.tokens
+                .claims_for(TokenKind::Access, user_id, Uuid::new_v4());
+
+        for role in roles {
+            for permission in role.permissions {
+                if !access_claims.permissions.contains(&permission) {
+                    access_claims.permissions.push(permission);
+                }
+            }
+
+            access_claims.roles.push(role.name);
+        }
+
+        let refresh_claims =
+            self.tokens
+                .claims_for(TokenKind::Refresh, user_id, refresh.id);
+
+        Ok(AuthTokens {
+            access_token: self.tokens.sign(TokenKind::Access, &access_claims)?,
+            access_expires_in: access_ttl,
+            refresh_token: self.tokens.sign(TokenKind::Refresh, &refresh_claims)?,
+            refresh_expires_in: refresh_ttl,
+        })
+    }
+}
@@ -0,0 +1,150 @@
+use chrono::Utc;
+use jsonwebtoken::{
+    decode, encode, errors::ErrorKind, DecodingKey, EncodingKey, Header, Validation,
+};
+use serde::{Deserialize, Serialize};
+use shaku::{Component, Interface};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::features::auth::domain::AuthError;
+use crate::shared::settings::SettingsProvider;
+
+// Access and refresh tokens are signed with different secrets and carry
+// their kind in the `typ` claim, so one kind of token is never accepted
+// where the other is expected, whatever the configuration.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TokenKind {
+    Access,
+    Refresh,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenClaims {
+    pub sub: Uuid,
+    pub jti: Uuid,
+    pub iat: i64,
+    pub exp: i64,
+    pub typ: TokenKind,
+    #[serde(default)]
+    pub roles: Vec<String>,
+    #[serde(default)]
+    pub permissions: Vec<String>,
+}
+
+pub trait TokenService: Interface {
+    fn sign(
+        &self,
+        kind: TokenKind,
+        claims: &TokenClaims,
+    ) -> Result<String, AuthError>;
+    fn verify(&self, kind: TokenKind, token: &str)
+        -> Result<TokenClaims, AuthError>;
+    fn lifetime(&self, kind: TokenKind) -> i64;
+
+    fn claims_for(&self, kind: TokenKind, user_id: Uuid, jti: Uuid) -> TokenClaims {
+        let iat = Utc::now().timestamp();
+
+        TokenClaims {
+            sub: user_id,
+            jti,
+            iat,
+            exp: iat + self.lifetime(kind),
+            typ: kind,
+            roles: Vec::new(),
+            permissions: Vec::new(),
+        }
+    }
+}
+
+#[derive(Component)]
+#[shaku(interface = TokenService)]
+pub struct JwtTokenService {
+    #[shaku(inject)]
+    settings: Arc<dyn SettingsProvider>,
+}
+
+impl JwtTokenService {
+    fn secret(&self, kind: TokenKind) -> &[u8] {
+        let auth = &self.settings.get().auth;
+
+        match kind {
+            TokenKind::Access => auth.access_secret.as_bytes(),
+            TokenKind::Refresh => auth.refresh_secret.as_bytes(),
+        }
+    }
+}
+
+impl TokenService for JwtTokenService {
+    fn sign(
+        &self,
+        kind: TokenKind,
+        claims: &TokenClaims,
+    ) -> Result<String, AuthError> {
+        let key = EncodingKey::from_secret(self.secret(kind));
+
+        encode(&Header::default(), claims, &key).map_err(AuthError::unexpected)
+    }
+
+    fn verify(
+        &self,
+        kind: TokenKind,
+        token: &str,
+    ) -> Result<TokenClaims, AuthError> {
+        let key = DecodingKey::from_secret(self.secret(kind));
+
+        let mut validation = Validation::default();
+        validation.leeway = 0;
+
+        let claims = decode::<TokenClaims>(token, &key, &validation)
+            .map(|data| data.claims)
+            .map_err(|e| match e.kind() {
+                ErrorKind::ExpiredSignature => AuthError::ExpiredToken,
+                _ => AuthError::InvalidToken,
+            })?;
+
+        if claims.typ != kind {
+            return Err(AuthError::InvalidToken);
+        }
+
+        Ok(claims)
+    }
+
+    fn lifetime(&self, kind: TokenKind) -> i64 {
+        let auth = &self.settings.get().auth;
+
+        match kind {
+            TokenKind::Access => auth.access_token_ttl,
+            TokenKind::Refresh => auth.refresh_token_ttl,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use shaku::HasComponent;
+
+    use super::*;
+    use crate::shared::infrastructure::testing::TestModuleBuilder;
+
+    #[tokio::test]
+    async fn rejects_a_token_of_the_other_kind_even_with_the_same_secret() {
+        let app = TestModuleBuilder::new()
+            .settings(|settings| {
+                settings.auth.refresh_secret = settings.auth.access_secret.clone()
+            })
+            .build();
+        let tokens: &dyn TokenService = app.module().resolve_ref();
+        let claims =
+            tokens.claims_for(TokenKind::Refresh, Uuid::new_v4(), Uuid::new_v4());
+        let refresh = tokens.sign(TokenKind::Refresh, &claims).unwrap();
+
+        assert!(tokens.verify(TokenKind::Refresh, &refresh).is_ok());
+        assert!(matches!(
+            tokens.verify(TokenKind::Access, &refresh),
+            Err(AuthError::InvalidToken)
+        ));
+    }
+}
@@ -0,0 +1,133 @@
+use async_trait::async_trait;
+use shaku::Component;
+use std::sync::{Arc, OnceLock};
+
+use crate::features::{
+    auth::{
+        application::{
+            interfaces::{LoginCase, LoginInput},
+            services::SessionService,
+        },
+        domain::{AuthError, AuthTokens},
+    },
+    user::{application::services::PasswordHasher, domain::UserRepository},
+};
+
+#[derive(Component)]
+#[shaku(interface = LoginCase)]
+pub struct LoginCaseImpl {
+    #[shaku(inject)]
+    users: Arc<dyn UserRepository>,
+    #[shaku(inject)]
+    hasher: Arc<dyn PasswordHasher>,
+    #[shaku(inject)]
+    sessions: Arc<dyn SessionService>,
+    #[shaku(default)]
+    dummy_hash: OnceLock<String>,
+}
+
+impl LoginCaseImpl {
+    // Hash verified when the email is unknown, so that request takes as
+    // long as one with a wrong password. It is hashed once, with the
+    // configured cost, the first time it is needed.
+
+    fn dummy_hash(&self) -> Result<&str, AuthError> {
+        if let Some(hash) = self.dummy_hash.get() {
+            return Ok(hash);
+        }
+
+        let hash = self.hasher.hash("dummy-password")?;
+        Ok(self.dummy_hash.get_or_init(|| hash))
+    }
+}
+
+#[async_trait]
+impl LoginCase for LoginCaseImpl {
+    async fn execute(&self, input: LoginInput) -> Result<AuthTokens, AuthError> {
+        // Unknown emails and wrong passwords produce the same error,
+        // and take the same time, so the endpoint can't be used to
+        // enumerate registered accounts
+
+        let Some(user) = self.users.find_by_email(&input.email).await? else {
+            self.hasher.verify(&input.password, self.dummy_hash()?)?;
+            return Err(AuthError::InvalidCredentials);
+        };
+
+        if !self.hasher.verify(&input.password, &user.password)? {
+            return Err(AuthError::InvalidCredentials);
+        }
+
+        user.ensure_validated()?;
+
+        self.sessions.open(user.id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use shaku::HasComponent;
+
+    use super::*;
+    use crate::features::user::domain::User;
+    use crate::shared::infrastructure::testing::{user, TestApp, TestModuleBuilder};
+
+    async fn register(app: &TestApp) {
+        app.users
+            .create(User {
+                password: app.hasher.hash("Secret123!").unwrap(),
+                validated: true,
+                ..user("alice", "alice@example.com")
+            })
+            .await
+            .unwrap();
+    }
+
+    fn input(email: &str, password: &str) -> LoginInput {
+        LoginInput {
+            email: email.to_string(),
+            password: password.to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn opens_a_session_with_the_right_password() {
+        let app = TestModuleBuilder::new().build();
+        let use_case: &dyn LoginCase = app.module().resolve_ref();
+        register(&app).await;
+
+        let tokens = use_case
+            .execute(input("alice@example.com", "Secret123!"))
+            .await
+            .unwrap();
+
+        assert!(!tokens.access_token.is_empty());
+        assert!(!tokens.refresh_token.is_empty());
+    }
+
+    #[tokio::test]
+    async fn rejects_a_wrong_password() {
+        let app = TestModuleBuilder::new().build();
+        let use_case: &dyn LoginCase = app.module().resolve_ref();
+        register(&app).await;
+
+        let result = use_case
+            .execute(input("alice@example.com", "Wrong123!"))
+            .await;
+
+        assert!(matches!(result, Err(AuthError::InvalidCredentials)));
+    }
+
+    #[tokio::test]
+    async fn rejects_an_unknown_email_after_verifying_a_password() {
+        let app = TestModuleBuilder::new().build();
+        let use_case: &dyn LoginCase = app.module().resolve_ref();
+        register(&app).await;
+
+        let result = use_case
+            .execute(input("bob@example.com", "Secret123!"))
+            .await;
+
+        assert!(matches!(result, Err(AuthError::InvalidCredentials)));
+        assert_eq!(app.hasher.verifications(), 1);
+    }
+}
@@ -0,0 +1,243 @@
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use shaku::Component;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::features::{
+    auth::{
+        application::interfaces::{
+            ForgotPasswordCase, ResetPasswordCase, ResetPasswordInput,
+        },
+        domain::{AuthError, AuthUnitOfWork, PasswordResetToken},
+    },
+    user::{application::services::PasswordHasher, domain::UserRepository},
+};
+use crate::shared::{
+    application::{generate_secure_token, hash_token, Email, Mailer},
+    domain::error_chain,
+    settings::SettingsProvider,
+};
+
+#[derive(Component)]
+#[shaku(interface = ForgotPasswordCase)]
+pub struct ForgotPasswordCaseImpl {
+    #[shaku(inject)]
+    users: Arc<dyn UserRepository>,
+    #[shaku(inject)]
+    unit_of_work: Arc<dyn AuthUnitOfWork>,
+    #[shaku(inject)]
+    mailer: Arc<dyn Mailer>,
+    #[shaku(inject)]
+    settings: Arc<dyn SettingsProvider>,
+}
+
+#[async_trait]
+impl ForgotPasswordCase for ForgotPasswordCaseImpl {
+    async fn execute(&self, email: String) -> Result<(), AuthError> {
+        let Some(user) = self.users.find_by_email(&email).await? else {
+            return Ok(());
+        };
+
+        // Only the last requested link is valid, the previous ones are
+        // kept if the new one can't be stored
+
+        let settings = self.settings.get();
+        let token = generate_secure_token();
+        let now = Utc::now();
+
+        let tx = self.unit_of_work.begin().await?;
+
+        tx.password_reset_tokens().delete_by_user(user.id).await?;
+        tx.password_reset_tokens()
+            .create(PasswordResetToken {
+                id: Uuid::new_v4(),
+                user_id: user.id,
+                token_hash: hash_token(&token),
+                expires_at: now
+                    + Duration::seconds(settings.auth.password_reset_token_ttl),
+                used_at: None,
+                created_at: now,
+            })
+            .await?;
+
+        tx.commit().await?;
+
+        let link =
+            format!("{}/reset-password?token={token}", settings.public.web_url);
+
+        // The response must not depend on whether the email is registered,
+        // so a failed delivery is only logged
+
+        let sent = self
+            .mailer
+            .send(Email {
+                to: user.email,
+                subject: "Reset your password".to_string(),
+                body: format!(
+                    "Hi {},\n\nSomeone requested a password reset for your \
+                     account. If it was you, open the following link to \
+                     choose a new password:\n\n{link}\n\nOtherwise you can \
+                     safely ignore this email.\n",
+                    user.username
+                ),
+            })
+            .await;
+
+        if let Err(error) = sent {
+            tracing::error!(
+                error = %error_chain(&error),
+                "failed to send the password reset email"
+            );
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Component)]
+#[shaku(interface = ResetPasswordCase)]
+pub struct ResetPasswordCaseImpl {
+    #[shaku(inject)]
+    hasher: Arc<dyn PasswordHasher>,
+    #[shaku(inject)]
+    unit_of_work: Arc<dyn AuthUnitOfWork>,
+}
+
+#[async_trait]
+impl ResetPasswordCase for ResetPasswordCaseImpl {
+    async fn execute(&self, input: ResetPasswordInput) -> Result<(), AuthError> {
+        let token_hash = hash_token(&input.token);
+
+        // The token is only spent if the password changes and the sessions
+        // opened with the old one (which may have leaked) are revoked
+
+        let tx = self.unit_of_work.begin().await?;
+
+        let Some(stored) =
+            tx.password_reset_tokens().find_by_hash(&token_hash).await?
+        else {
+            return Err(AuthError::InvalidResetToken);
+        };
+
+        if stored.used_at.is_some() || stored.expires_at <= Utc::now() {
+            return Err(AuthError::InvalidResetToken);
+        }
+
+        let Some(mut user) = tx.users().find_by_id(stored.user_id).await? else {
+            return Err(AuthError::InvalidResetToken);
+        };
+
+        if !tx.password_reset_tokens().mark_used(stored.id).await? {
+            return Err(AuthError::InvalidResetToken);
+        }
+
+        user.password = self.hasher.hash(&input.password)?;
+        user.updated_at = Utc::now();
+        tx.users().update(user).await?;
+        tx.refresh_tokens()
+            .revoke_all_by_user(stored.user_id)
+            .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use shaku::HasComponent;
+
+    use super::*;
+    use crate::features::auth::{
+        application::{interfaces::RefreshTokenCase, services::SessionService},
+        domain::PasswordResetTokenRepository,
+    };
+    use crate::features::user::domain::User;
+    use crate::shared::infrastructure::testing::{user, TestApp, TestModuleBuilder};
+
+    async fn register(app: &TestApp) {
+        app.users
+            .create(User {
+                validated: true,
+                ..user("alice", "alice@example.com")
+            })
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn emails_a_reset_link_to_registered_accounts_only() {
+        let app = TestModuleBuilder::new()
+            .settings(|settings| {
+                settings.public.web_url = "https://example.com".to_string()
+            })
+            .build();
+        let use_case: &dyn ForgotPasswordCase = app.module().resolve_ref();
+        register(&app).await;
+
+        use_case
+            .execute("bob@example.com".to_string())
+            .await
+            .unwrap();
+        use_case
+            .execute("alice@example.com".to_string())
+            .await
+            .unwrap();
+        let sent = app.mailer.sent();
+
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].to, "alice@example.com");
+        assert!(sent[0]
+            .body
+            .contains("https://example.com/reset-password?token="));
+    }
+
+    #[tokio::test]
+    async fn answers_the_same_when_the_email_cannot_be_sent() {
+        let app = TestModuleBuilder::new().build();
+        let use_case: &dyn ForgotPasswordCase = app.module().resolve_ref();
+        register(&app).await;
+        app.mailer.fail_sends(true);
+
+        let result = use_case.execute("alice@example.com".to_string()).await;
+
+        assert!(result.is_ok());
+        assert!(app.mailer.sent().is_empty());
+    }
+
+    #[tokio::test]
+    async fn keeps_the_token_and_the_sessions_when_the_password_is_not_saved() {
+        let app = TestModuleBuilder::new().build();
+        let forgot: &dyn ForgotPasswordCase = app.module().resolve_ref();
+        let reset: &dyn ResetPasswordCase = app.module().resolve_ref();
+        let refresh: &dyn RefreshTokenCase = app.module().resolve_ref();
+        let sessions: &dyn SessionService = app.module().resolve_ref();
+        let tokens: &dyn PasswordResetTokenRepository = app.module().resolve_ref();
+        register(&app).await;
+
+        let alice = app.users.find_by_email("alice@example.com").await.unwrap();
+        let session = sessions.open(alice.unwrap().id).await.unwrap();
+
+        forgot
+            .execute("alice@example.com".to_string())
+            .await
+            .unwrap();
+        let body = &app.mailer.sent()[0].body;
+        let token = body.split("token=").nth(1).unwrap().lines().next().unwrap();
+
+        app.users.fail_updates(true);
+        let result = reset
+            .execute(ResetPasswordInput {
+                token: token.to_string(),
+                password: "N3w-password".to_string(),
+            })
+            .await;
+
+        let stored = tokens.find_by_hash(&hash_token(token)).await.unwrap();
+
+        assert!(result.is_err());
+        assert!(stored.unwrap().used_at.is_none());
+        assert!(refresh.execute(session.refresh_token).await.is_ok());
+    }
+}
@@ -0,0 +1,91 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use shaku::Component;
+use std::sync::Arc;
+
+use crate::features::{
+    auth::{
+        application::{
+            interfaces::RefreshTokenCase,
+            services::{SessionService, TokenKind, TokenService},
+        },
+        domain::{AuthError, AuthTokens, RefreshTokenRepository},
+    },
+    user::domain::UserRepository,
+};
+
+#[derive(Component)]
+#[shaku(interface = RefreshTokenCase)]
+pub struct RefreshTokenCaseImpl {
+    #[shaku(inject)]
+    tokens: Arc<dyn TokenService>,
+    #[shaku(inject)]
+    repository: Arc<dyn RefreshTokenRepository>,
+    #[shaku(inject)]
+    users: Arc<dyn UserRepository>,
+    #[shaku(inject)]
+    sessions: Arc<dyn SessionService>,
+}
+
+#[async_trait]
+impl RefreshTokenCase for RefreshTokenCaseImpl {
+    async fn execute(&self, refresh_token: String) -> Result<AuthTokens, AuthError> {
+        let claims = self.tokens.verify(TokenKind::Refresh, &refresh_token)?;
+
+        let Some(stored) = self.repository.find_by_id(claims.jti).await? else {
+            return Err(AuthError::InvalidToken);
+        };
+
+        if stored.user_id != claims.sub || stored.expires_at <= Utc::now() {
+            return Err(AuthError::InvalidToken);
+        }
+
+        // A refresh token can only be rotated once. Presenting an already
+        // revoked token means it was leaked, so every session of the user
+        // is revoked and the user has to log in again
+
+        if stored.revoked || !self.repository.revoke(stored.id).await? {
+            self.repository.revoke_all_by_user(stored.user_id).await?;
+            return Err(AuthError::InvalidToken);
+        }
+
+        // Soft-deleted users are not found either, their sessions end when
+        // the access token expires (see `restore_user`)
+
+        if self.users.find_by_id(stored.user_id).await?.is_none() {
+            return Err(AuthError::InvalidToken);
+        }
+
+        self.sessions.open(stored.user_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use shaku::HasComponent;
+
+    use super::*;
+    use crate::shared::infrastructure::testing::{user, TestModuleBuilder};
+
+    #[tokio::test]
+    async fn deleted_users_can_not_refresh_their_session() {
+        let app = TestModuleBuilder::new().build();
+        let use_case: &dyn RefreshTokenCase = app.module().resolve_ref();
+        let sessions: &dyn SessionService = app.module().resolve_ref();
+        let alice = app
+            .users
+            .create(user("alice", "alice@example.com"))
+            .await
+            .unwrap();
+
+        let active = sessions.open(alice.id).await.unwrap();
+        let deleted = sessions.open(alice.id).await.unwrap();
+
+        assert!(use_case.execute(active.refresh_token).await.is_ok());
+
+        app.users.soft_delete(alice.id).await.unwrap();
+        let refreshed = use_case.execute(deleted.refresh_token).await;
+
+        assert!(matches!(refreshed, Err(AuthError::InvalidToken)));
+    }
+}
@@ -0,0 +1,39 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+// A refresh token issued to a user. Only its id (the `jti` claim of the
+// signed token) is persisted, so a token can be revoked once it has been
+// rotated or when the whole session family has to be invalidated.
+
+#[derive(Debug, Clone)]
+pub struct RefreshToken {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub revoked: bool,
+    pub expires_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+// The pair of signed tokens returned to the client after a successful
+// login or refresh. Lifetimes are expressed in seconds.
+
+#[derive(Debug, Clone)]
+pub struct AuthTokens {
+    pub access_token: String,
+    pub access_expires_in: i64,
+    pub refresh_token: String,
+    pub refresh_expires_in: i64,
+}
+
+// A single-use token sent by email to reset a forgotten password.
+// Only the hash of the token is stored.
+
+#[derive(Debug, Clone)]
+pub struct PasswordResetToken {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub token_hash: String,
+    pub expires_at: DateTime<Utc>,
+    pub used_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
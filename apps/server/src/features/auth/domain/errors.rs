@@ -0,0 +1,88 @@
+use std::{error::Error, fmt};
+
+use crate::features::{role::domain::RoleError, user::domain::UserError};
+use crate::shared::domain::{BoxError, TransactionError};
+
+#[derive(Debug)]
+pub enum AuthError {
+    InvalidCredentials,
+    EmailNotVerified,
+    MissingToken,
+    InvalidToken,
+    ExpiredToken,
+    Forbidden,
+    InvalidResetToken,
+    DatabaseUnavailable(BoxError),
+    UnexpectedError(BoxError),
+}
+
+impl AuthError {
+    pub fn unexpected(error: impl Into<BoxError>) -> Self {
+        AuthError::UnexpectedError(error.into())
+    }
+}
+
+impl fmt::Display for AuthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AuthError::InvalidCredentials => write!(f, "invalid credentials"),
+            AuthError::EmailNotVerified => write!(f, "email not verified"),
+            AuthError::MissingToken => write!(f, "missing token"),
+            AuthError::InvalidToken => write!(f, "invalid token"),
+            AuthError::ExpiredToken => write!(f, "expired token"),
+            AuthError::Forbidden => write!(f, "forbidden"),
+            AuthError::InvalidResetToken => write!(f, "invalid reset token"),
+            AuthError::DatabaseUnavailable(_) => write!(f, "database unavailable"),
+            AuthError::UnexpectedError(_) => write!(f, "unexpected error"),
+        }
+    }
+}
+
+impl Error for AuthError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            AuthError::DatabaseUnavailable(source)
+            | AuthError::UnexpectedError(source) => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+// The auth use cases read users and roles through the `UserRepository`
+// and `RoleRepository`, besides unverified accounts any failure there
+// is unexpected from the auth point of view. The cause is kept, and an
+// unreachable database is still reported as such.
+
+impl From<UserError> for AuthError {
+    fn from(error: UserError) -> Self {
+        match error {
+            UserError::NotValidated => AuthError::EmailNotVerified,
+            UserError::DatabaseUnavailable(source) => {
+                AuthError::DatabaseUnavailable(source)
+            }
+            error => AuthError::unexpected(error),
+        }
+    }
+}
+
+impl From<RoleError> for AuthError {
+    fn from(error: RoleError) -> Self {
+        match error {
+            RoleError::DatabaseUnavailable(source) => {
+                AuthError::DatabaseUnavailable(source)
+            }
+            error => AuthError::unexpected(error),
+        }
+    }
+}
+
+impl From<TransactionError> for AuthError {
+    fn from(error: TransactionError) -> Self {
+        match error {
+            TransactionError::Unavailable(source) => {
+                AuthError::DatabaseUnavailable(source)
+            }
+            TransactionError::Failed(_) => AuthError::unexpected(error),
+        }
+    }
+}
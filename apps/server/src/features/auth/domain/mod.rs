@@ -0,0 +1,9 @@
+mod entity;
+mod errors;
+mod repository;
+mod transaction;
+
+pub use entity::*;
+pub use errors::*;
+pub use repository::*;
+pub use transaction::*;
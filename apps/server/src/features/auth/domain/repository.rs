@@ -0,0 +1,36 @@
+use async_trait::async_trait;
+use shaku::Interface;
+use uuid::Uuid;
+
+use super::{
+    entity::{PasswordResetToken, RefreshToken},
+    errors::AuthError,
+};
+
+// `revoke` returns whether the token was still active, so rotating the
+// same refresh token twice (e.g. on concurrent requests) is detectable.
+
+#[async_trait]
+pub trait RefreshTokenRepository: Interface {
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<RefreshToken>, AuthError>;
+    async fn create(&self, token: RefreshToken) -> Result<RefreshToken, AuthError>;
+    async fn revoke(&self, id: Uuid) -> Result<bool, AuthError>;
+    async fn revoke_all_by_user(&self, user_id: Uuid) -> Result<(), AuthError>;
+}
+
+// `mark_used` returns whether the token was still unused, so the same
+// reset link can't be consumed twice.
+
+#[async_trait]
+pub trait PasswordResetTokenRepository: Interface {
+    async fn find_by_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<PasswordResetToken>, AuthError>;
+    async fn create(
+        &self,
+        token: PasswordResetToken,
+    ) -> Result<PasswordResetToken, AuthError>;
+    async fn mark_used(&self, id: Uuid) -> Result<bool, AuthError>;
+    async fn delete_by_user(&self, user_id: Uuid) -> Result<(), AuthError>;
+}
@@ -0,0 +1,22 @@
+use async_trait::async_trait;
+use shaku::Interface;
+
+use super::repository::{PasswordResetTokenRepository, RefreshTokenRepository};
+use crate::features::user::domain::UserRepository;
+use crate::shared::domain::{Transaction, TransactionError};
+
+// Unit of work of the auth feature (see `shared::domain::Transaction`)
+
+#[async_trait]
+pub trait AuthUnitOfWork: Interface {
+    async fn begin(&self) -> Result<Box<dyn AuthTransaction>, TransactionError>;
+}
+
+// A password reset changes the user and ends its sessions, so the users
+// are written in the same transaction as the tokens
+
+pub trait AuthTransaction: Transaction {
+    fn users(&self) -> &dyn UserRepository;
+    fn refresh_tokens(&self) -> &dyn RefreshTokenRepository;
+    fn password_reset_tokens(&self) -> &dyn PasswordResetTokenRepository;
+}
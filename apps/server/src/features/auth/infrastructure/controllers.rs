@@ -0,0 +1,145 @@
+use axum::http::StatusCode;
+use axum_extra::extract::cookie::CookieJar;
+use axum_responses::http::{ControllerResult, HttpResponse};
+use serde_json::json;
+
+use crate::{
+    features::auth::{
+        application::interfaces::{
+            ForgotPasswordCase, LoginCase, RefreshTokenCase, ResetPasswordCase,
+        },
+        domain::{AuthError, AuthTokens},
+        infrastructure::{
+            dtos::{ForgotPasswordDto, LoginDto, ResetPasswordDto},
+            openapi::TokensBody,
+        },
+    },
+    shared::infrastructure::{
+        extractors::{BodyValidator, UseCase},
+        openapi::MessageResponse,
+    },
+};
+
+// The refresh token never travels in a response body, it is stored in an
+// HttpOnly cookie so the browser sends it back on `POST /auth/refresh`.
+
+const REFRESH_TOKEN_COOKIE: &str = "refresh_token";
+
+fn tokens_response(tokens: AuthTokens) -> ControllerResult {
+    let cookie = format!(
+        "{REFRESH_TOKEN_COOKIE}={}; Max-Age={}; Path=/; HttpOnly; Secure; SameSite=Strict",
+        tokens.refresh_token, tokens.refresh_expires_in
+    );
+
+    HttpResponse::build()
+        .status(StatusCode::OK)
+        .add_header("Set-Cookie", &cookie)
+        .body(json!({
+            "data": {
+                "accessToken": tokens.access_token,
+                "tokenType": "Bearer",
+                "expiresIn": tokens.access_expires_in,
+            }
+        }))
+        .wrap()
+}
+
+#[utoipa::path(
+    post,
+    path = "/auth/login",
+    tag = "auth",
+    request_body = LoginDto,
+    responses((
+        status = 200,
+        description = "A new access token, the refresh token is set in the \
+                       `refresh_token` HttpOnly cookie",
+        body = TokensBody,
+        headers(("Set-Cookie" = String, description = "The refresh token"))
+    ))
+)]
+pub async fn login(
+    use_case: UseCase<dyn LoginCase>,
+    BodyValidator(credentials): BodyValidator<LoginDto>,
+) -> ControllerResult {
+    let tokens = use_case
+        .run(|use_case| use_case.execute(credentials.into()))
+        .await?;
+    tokens_response(tokens)
+}
+
+#[utoipa::path(
+    post,
+    path = "/auth/refresh",
+    tag = "auth",
+    params((
+        "refresh_token" = String,
+        Cookie,
+        description = "Set by the login and by the previous refresh"
+    )),
+    responses((
+        status = 200,
+        description = "A new access token, the refresh token is set in the \
+                       `refresh_token` HttpOnly cookie",
+        body = TokensBody,
+        headers(("Set-Cookie" = String, description = "The refresh token"))
+    ))
+)]
+pub async fn refresh(
+    use_case: UseCase<dyn RefreshTokenCase>,
+    jar: CookieJar,
+) -> ControllerResult {
+    let Some(cookie) = jar.get(REFRESH_TOKEN_COOKIE) else {
+        return Err(AuthError::InvalidToken.into());
+    };
+
+    let tokens = use_case
+        .run(|use_case| use_case.execute(cookie.value().to_string()))
+        .await?;
+    tokens_response(tokens)
+}
+
+#[utoipa::path(
+    post,
+    path = "/auth/password/forgot",
+    tag = "auth",
+    request_body = ForgotPasswordDto,
+    responses((
+        status = 202,
+        description = "Sent regardless of the account existing, to not disclose it",
+        body = MessageResponse
+    ))
+)]
+pub async fn forgot_password(
+    use_case: UseCase<dyn ForgotPasswordCase>,
+    BodyValidator(dto): BodyValidator<ForgotPasswordDto>,
+) -> ControllerResult {
+    use_case.run(|use_case| use_case.execute(dto.email)).await?;
+
+    HttpResponse::build()
+        .status(StatusCode::ACCEPTED)
+        .body(json!({
+            "message": "If the account exists, a reset link was sent to its email"
+        }))
+        .wrap()
+}
+
+#[utoipa::path(
+    post,
+    path = "/auth/password/reset",
+    tag = "auth",
+    request_body = ResetPasswordDto,
+    responses((status = 200, description = "The password was updated", body = MessageResponse))
+)]
+pub async fn reset_password(
+    use_case: UseCase<dyn ResetPasswordCase>,
+    BodyValidator(dto): BodyValidator<ResetPasswordDto>,
+) -> ControllerResult {
+    use_case
+        .run(|use_case| use_case.execute(dto.into()))
+        .await?;
+
+    HttpResponse::build()
+        .status(StatusCode::OK)
+        .body(json!({ "message": "Password updated successfully" }))
+        .wrap()
+}
@@ -0,0 +1,82 @@
+// This module contains the data transfer objects (DTOs) for the auth endpoints.
+
+use serde::Deserialize;
+use utoipa::ToSchema;
+use validator::{Validate, ValidationError};
+
+use crate::features::{
+    auth::application::interfaces::{LoginInput, ResetPasswordInput},
+    user::infrastructure::{password_field, password_schema},
+};
+use crate::shared::infrastructure::validation::invalid_field;
+
+// Only the shape of the credentials is validated here, the password
+// rules are enforced when the password is set, not when it is checked.
+
+#[derive(Deserialize, Validate, ToSchema)]
+pub struct LoginDto {
+    #[validate(email)]
+    #[schema(format = Email)]
+    pub email: String,
+    #[validate(length(min = 1, max = 100))]
+    #[schema(format = Password, min_length = 1, max_length = 100)]
+    pub password: String,
+}
+
+// | Controller (LoginDto) -> Use Case (LoginInput) |
+
+impl From<LoginDto> for LoginInput {
+    fn from(dto: LoginDto) -> Self {
+        LoginInput {
+            email: dto.email,
+            password: dto.password,
+        }
+    }
+}
+
+#[derive(Deserialize, Validate, ToSchema)]
+pub struct ForgotPasswordDto {
+    #[validate(email)]
+    #[schema(format = Email)]
+    pub email: String,
+}
+
+// The new password follows the same rules used when registering
+
+#[derive(Deserialize, Validate, ToSchema)]
+#[validate(schema(function = "validate_reset_password_pairs"))]
+pub struct ResetPasswordDto {
+    /// Token of the link sent by email
+    #[validate(length(min = 1, max = 128))]
+    #[schema(min_length = 1, max_length = 128)]
+    pub token: String,
+    #[validate(custom(function = "password_schema"))]
+    #[schema(schema_with = password_field)]
+    pub password: String,
+    #[validate(custom(function = "password_schema"))]
+    #[serde(rename = "confirmPassword")]
+    #[schema(schema_with = password_field)]
+    /// Must be equal to `password`
+    pub confirm_password: String,
+}
+
+fn validate_reset_password_pairs(
+    dto: &ResetPasswordDto,
+) -> Result<(), ValidationError> {
+    if dto.password != dto.confirm_password {
+        return Err(invalid_field("confirmPassword", "passwords_mismatch"));
+    }
+
+    Ok(())
+}
+
+// | Controller (ResetPasswordDto) -> Use Case (ResetPasswordInput) |
+
+impl From<ResetPasswordDto> for ResetPasswordInput {
+    fn from(dto: ResetPasswordDto) -> Self {
+        ResetPasswordInput {
+            token: dto.token,
+            password: dto.password,
+        }
+    }
+}
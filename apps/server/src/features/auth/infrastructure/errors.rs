@@ -0,0 +1,74 @@
+// This file implements the conversion from the `AuthError` enum
+// to the `HttpResponse` type, an `application/problem+json` document
+// (see `shared::infrastructure::problem`). The messages of each code
+// live in the catalogs, `locales/<lang>.toml`.
+
+use axum::http::StatusCode;
+use axum_responses::http::HttpResponse;
+
+use crate::features::auth::domain::AuthError;
+use crate::shared::{domain::error_chain, infrastructure::problem::Problem};
+
+// `openapi.rs` documents the error responses of the auth routes, and the
+// ones every protected route shares, with these same problems.
+
+impl From<&AuthError> for Problem {
+    fn from(value: &AuthError) -> Self {
+        match value {
+            AuthError::InvalidCredentials => {
+                Problem::new(StatusCode::UNAUTHORIZED, "auth.invalid_credentials")
+            }
+
+            AuthError::EmailNotVerified => {
+                Problem::new(StatusCode::FORBIDDEN, "auth.email_not_verified")
+                    .field("email")
+            }
+
+            AuthError::MissingToken => {
+                Problem::new(StatusCode::UNAUTHORIZED, "auth.missing_token")
+            }
+
+            AuthError::InvalidToken => {
+                Problem::new(StatusCode::UNAUTHORIZED, "auth.invalid_token")
+            }
+
+            AuthError::ExpiredToken => {
+                Problem::new(StatusCode::UNAUTHORIZED, "auth.expired_token")
+            }
+
+            AuthError::Forbidden => {
+                Problem::new(StatusCode::FORBIDDEN, "auth.forbidden")
+            }
+
+            AuthError::InvalidResetToken => {
+                Problem::new(StatusCode::BAD_REQUEST, "auth.invalid_reset_token")
+                    .field("token")
+            }
+
+            AuthError::DatabaseUnavailable(_) => Problem::new(
+                StatusCode::SERVICE_UNAVAILABLE,
+                "server.database_unavailable",
+            ),
+
+            AuthError::UnexpectedError(_) => Problem::new(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "server.unexpected_error",
+            ),
+        }
+    }
+}
+
+// The client only gets the generic problem, the cause is logged
+
+impl From<AuthError> for HttpResponse {
+    fn from(value: AuthError) -> Self {
+        if matches!(
+            value,
+            AuthError::DatabaseUnavailable(_) | AuthError::UnexpectedError(_)
+        ) {
+            tracing::error!(error = %error_chain(&value), "auth operation failed");
+        }
+
+        Problem::from(&value).into()
+    }
+}
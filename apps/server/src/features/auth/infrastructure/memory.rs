@@ -0,0 +1,186 @@
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use async_trait::async_trait;
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::features::{
+    auth::domain::{
+        AuthError, AuthTransaction, AuthUnitOfWork, PasswordResetToken,
+        PasswordResetTokenRepository, RefreshToken, RefreshTokenRepository,
+    },
+    user::{domain::UserRepository, infrastructure::InMemoryUserRepository},
+};
+use crate::shared::{
+    domain::{Transaction, TransactionError},
+    infrastructure::testing::RollbackLog,
+};
+
+// Repositories that keep the auth tokens in memory, for the tests (see
+// `TestModuleBuilder`). Clones share the same storage.
+
+#[derive(Clone, Default)]
+pub struct InMemoryRefreshTokenRepository {
+    tokens: Arc<Mutex<Vec<RefreshToken>>>,
+}
+
+impl InMemoryRefreshTokenRepository {
+    fn tokens(&self) -> MutexGuard<'_, Vec<RefreshToken>> {
+        self.tokens.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn restore_on_rollback(&self, log: &mut RollbackLog) {
+        let (repository, tokens) = (self.clone(), self.tokens().clone());
+        log.on_rollback(move || *repository.tokens() = tokens);
+    }
+}
+
+#[async_trait]
+impl RefreshTokenRepository for InMemoryRefreshTokenRepository {
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<RefreshToken>, AuthError> {
+        Ok(self.tokens().iter().find(|token| token.id == id).cloned())
+    }
+
+    async fn create(&self, token: RefreshToken) -> Result<RefreshToken, AuthError> {
+        self.tokens().push(token.clone());
+        Ok(token)
+    }
+
+    async fn revoke(&self, id: Uuid) -> Result<bool, AuthError> {
+        let mut tokens = self.tokens();
+
+        match tokens
+            .iter_mut()
+            .find(|token| token.id == id && !token.revoked)
+        {
+            Some(token) => {
+                token.revoked = true;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    async fn revoke_all_by_user(&self, user_id: Uuid) -> Result<(), AuthError> {
+        self.tokens()
+            .iter_mut()
+            .filter(|token| token.user_id == user_id)
+            .for_each(|token| token.revoked = true);
+
+        Ok(())
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct InMemoryPasswordResetTokenRepository {
+    tokens: Arc<Mutex<Vec<PasswordResetToken>>>,
+}
+
+impl InMemoryPasswordResetTokenRepository {
+    fn tokens(&self) -> MutexGuard<'_, Vec<PasswordResetToken>> {
+        self.tokens.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn restore_on_rollback(&self, log: &mut RollbackLog) {
+        let (repository, tokens) = (self.clone(), self.tokens().clone());
+        log.on_rollback(move || *repository.tokens() = tokens);
+    }
+}
+
+#[async_trait]
+impl PasswordResetTokenRepository for InMemoryPasswordResetTokenRepository {
+    async fn find_by_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<PasswordResetToken>, AuthError> {
+        Ok(self
+            .tokens()
+            .iter()
+            .find(|token| token.token_hash == token_hash)
+            .cloned())
+    }
+
+    async fn create(
+        &self,
+        token: PasswordResetToken,
+    ) -> Result<PasswordResetToken, AuthError> {
+        self.tokens().push(token.clone());
+        Ok(token)
+    }
+
+    async fn mark_used(&self, id: Uuid) -> Result<bool, AuthError> {
+        let mut tokens = self.tokens();
+
+        match tokens
+            .iter_mut()
+            .find(|token| token.id == id && token.used_at.is_none())
+        {
+            Some(token) => {
+                token.used_at = Some(Utc::now());
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    async fn delete_by_user(&self, user_id: Uuid) -> Result<(), AuthError> {
+        self.tokens().retain(|token| token.user_id != user_id);
+        Ok(())
+    }
+}
+
+// `AuthUnitOfWork` over the repositories above, restored if the transaction
+// is rolled back (see `RollbackLog`)
+
+#[derive(Clone, Default)]
+pub struct InMemoryAuthUnitOfWork {
+    pub users: InMemoryUserRepository,
+    pub refresh_tokens: InMemoryRefreshTokenRepository,
+    pub password_reset_tokens: InMemoryPasswordResetTokenRepository,
+}
+
+#[async_trait]
+impl AuthUnitOfWork for InMemoryAuthUnitOfWork {
+    async fn begin(&self) -> Result<Box<dyn AuthTransaction>, TransactionError> {
+        let mut log = RollbackLog::default();
+        self.users.restore_on_rollback(&mut log);
+        self.refresh_tokens.restore_on_rollback(&mut log);
+        self.password_reset_tokens.restore_on_rollback(&mut log);
+
+        Ok(Box::new(InMemoryAuthTransaction {
+            repositories: self.clone(),
+            log,
+        }))
+    }
+}
+
+struct InMemoryAuthTransaction {
+    repositories: InMemoryAuthUnitOfWork,
+    log: RollbackLog,
+}
+
+#[async_trait]
+impl Transaction for InMemoryAuthTransaction {
+    async fn commit(self: Box<Self>) -> Result<(), TransactionError> {
+        self.log.commit();
+        Ok(())
+    }
+
+    async fn rollback(self: Box<Self>) -> Result<(), TransactionError> {
+        Ok(())
+    }
+}
+
+impl AuthTransaction for InMemoryAuthTransaction {
+    fn users(&self) -> &dyn UserRepository {
+        &self.repositories.users
+    }
+
+    fn refresh_tokens(&self) -> &dyn RefreshTokenRepository {
+        &self.repositories.refresh_tokens
+    }
+
+    fn password_reset_tokens(&self) -> &dyn PasswordResetTokenRepository {
+        &self.repositories.password_reset_tokens
+    }
+}
@@ -0,0 +1,17 @@
+mod controllers;
+mod dtos;
+mod errors;
+#[cfg(test)]
+mod memory;
+mod models;
+mod openapi;
+mod repository;
+mod routes;
+mod transaction;
+
+#[cfg(test)]
+pub use memory::*;
+pub use openapi::{authentication_problems, permission_problems, AuthApi};
+pub use repository::*;
+pub use routes::router as auth_router;
+pub use transaction::*;
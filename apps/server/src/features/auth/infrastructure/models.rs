@@ -0,0 +1,52 @@
+// This module contains the RefreshTokenModel and PasswordResetTokenModel
+// structs and its conversions traits.
+
+use chrono::{DateTime, Utc};
+use sqlx::prelude::FromRow;
+use uuid::Uuid;
+
+use crate::features::auth::domain::{PasswordResetToken, RefreshToken};
+
+#[derive(FromRow, Debug, Clone)]
+pub struct RefreshTokenModel {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub revoked: bool,
+    pub expires_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<RefreshTokenModel> for RefreshToken {
+    fn from(model: RefreshTokenModel) -> Self {
+        RefreshToken {
+            id: model.id,
+            user_id: model.user_id,
+            revoked: model.revoked,
+            expires_at: model.expires_at,
+            created_at: model.created_at,
+        }
+    }
+}
+
+#[derive(FromRow, Debug, Clone)]
+pub struct PasswordResetTokenModel {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub token_hash: String,
+    pub expires_at: DateTime<Utc>,
+    pub used_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<PasswordResetTokenModel> for PasswordResetToken {
+    fn from(model: PasswordResetTokenModel) -> Self {
+        PasswordResetToken {
+            id: model.id,
+            user_id: model.user_id,
+            token_hash: model.token_hash,
+            expires_at: model.expires_at,
+            used_at: model.used_at,
+            created_at: model.created_at,
+        }
+    }
+}
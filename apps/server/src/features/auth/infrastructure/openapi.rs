@@ -0,0 +1,144 @@
+// OpenAPI documentation of the auth routes, merged into the specification
+// by `shared::infrastructure::openapi`.
+
+use utoipa::{
+    openapi::{path::HttpMethod, OpenApi as Spec},
+    Modify, OpenApi, ToSchema,
+};
+
+use super::controllers::*;
+use super::dtos::{ForgotPasswordDto, LoginDto, ResetPasswordDto};
+use crate::features::auth::domain::AuthError;
+use crate::shared::infrastructure::{
+    openapi::{body_problems, document_problems},
+    problem::Problem,
+};
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(login, refresh, forgot_password, reset_password),
+    components(schemas(LoginDto, ForgotPasswordDto, ResetPasswordDto)),
+    tags((name = "auth", description = "Sessions and password recovery")),
+    modifiers(&AuthProblems)
+)]
+pub struct AuthApi;
+
+#[allow(dead_code)]
+#[derive(ToSchema)]
+pub struct TokensBody {
+    data: AccessToken,
+}
+
+#[allow(dead_code)]
+#[derive(ToSchema)]
+#[schema(rename_all = "camelCase")]
+struct AccessToken {
+    /// JWT to send as `Authorization: Bearer <accessToken>`
+    access_token: String,
+    /// Always `Bearer`
+    token_type: String,
+    /// Seconds until the access token expires
+    expires_in: i64,
+}
+
+struct AuthProblems;
+
+impl Modify for AuthProblems {
+    fn modify(&self, spec: &mut Spec) {
+        use AuthError::*;
+
+        document_problems(
+            spec,
+            "/auth/login",
+            HttpMethod::Post,
+            [
+                body_problems(),
+                problems(
+                    [InvalidCredentials, EmailNotVerified]
+                        .into_iter()
+                        .chain(failures()),
+                ),
+            ]
+            .concat(),
+        );
+
+        document_problems(
+            spec,
+            "/auth/refresh",
+            HttpMethod::Post,
+            problems([InvalidToken, ExpiredToken].into_iter().chain(failures())),
+        );
+
+        document_problems(
+            spec,
+            "/auth/password/forgot",
+            HttpMethod::Post,
+            [body_problems(), problems(failures())].concat(),
+        );
+
+        document_problems(
+            spec,
+            "/auth/password/reset",
+            HttpMethod::Post,
+            [
+                body_problems(),
+                problems([InvalidResetToken].into_iter().chain(failures())),
+            ]
+            .concat(),
+        );
+    }
+}
+
+// Every auth route reaches the database
+
+fn failures() -> [AuthError; 2] {
+    [
+        AuthError::DatabaseUnavailable("".into()),
+        AuthError::unexpected(""),
+    ]
+}
+
+fn problems(errors: impl IntoIterator<Item = AuthError>) -> Vec<Problem> {
+    errors
+        .into_iter()
+        .map(|error| Problem::from(&error))
+        .collect()
+}
+
+// Problems of the routes behind `require_auth`/`require_permission` or
+// extracting an `AuthUser`, the other features add them to theirs
+
+pub fn authentication_problems() -> Vec<Problem> {
+    use AuthError::*;
+    problems([MissingToken, InvalidToken, ExpiredToken])
+}
+
+pub fn permission_problems() -> Vec<Problem> {
+    problems([AuthError::Forbidden])
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::shared::infrastructure::testing::{
+        assert_documented_bounds, schema_properties,
+    };
+
+    #[test]
+    fn documented_bounds_match_the_validation() {
+        assert_documented_bounds::<LoginDto>(
+            &schema_properties("LoginDto"),
+            json!({ "email": "alice@example.com", "password": "" }),
+        );
+        assert_documented_bounds::<ForgotPasswordDto>(
+            &schema_properties("ForgotPasswordDto"),
+            json!({ "email": "alice@example.com" }),
+        );
+        assert_documented_bounds::<ResetPasswordDto>(
+            &schema_properties("ResetPasswordDto"),
+            json!({ "token": "", "password": "Aa1!", "confirmPassword": "Aa1!" }),
+        );
+    }
+}
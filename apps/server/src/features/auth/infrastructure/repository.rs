@@ -0,0 +1,243 @@
+use async_trait::async_trait;
+use shaku::Component;
+use std::sync::Arc;
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::shared::infrastructure::{
+    is_unavailable, scoped_connection, DatabaseConnection, PgTransaction,
+    ScopedConnection,
+};
+
+use crate::features::auth::{
+    domain::{
+        AuthError, PasswordResetToken, PasswordResetTokenRepository, RefreshToken,
+        RefreshTokenRepository,
+    },
+    infrastructure::models::{PasswordResetTokenModel, RefreshTokenModel},
+};
+
+#[derive(Component)]
+#[shaku(interface = RefreshTokenRepository)]
+pub struct PostgresRefreshTokenRepository {
+    #[shaku(inject)]
+    database_connection: Arc<dyn DatabaseConnection>,
+    #[shaku(default)]
+    transaction: Option<PgTransaction>,
+}
+
+impl PostgresRefreshTokenRepository {
+    pub fn in_transaction(
+        database_connection: Arc<dyn DatabaseConnection>,
+        transaction: PgTransaction,
+    ) -> Self {
+        Self {
+            database_connection,
+            transaction: Some(transaction),
+        }
+    }
+
+    async fn connection(&self) -> Result<ScopedConnection, AuthError> {
+        scoped_connection(
+            self.database_connection.as_ref(),
+            self.transaction.as_ref(),
+        )
+        .await
+        .map_err(map_db_error)
+    }
+}
+
+#[async_trait]
+impl RefreshTokenRepository for PostgresRefreshTokenRepository {
+    #[instrument(name = "RefreshTokenRepository::find_by_id", skip_all)]
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<RefreshToken>, AuthError> {
+        let mut connection = self.connection().await?;
+        let query = r#"SELECT * FROM refresh_tokens WHERE id = $1"#;
+
+        let token = sqlx::query_as::<_, RefreshTokenModel>(query)
+            .bind(id)
+            .fetch_optional(&mut *connection)
+            .await
+            .map_err(map_db_error)?;
+
+        Ok(token.map(RefreshToken::from))
+    }
+
+    #[instrument(name = "RefreshTokenRepository::create", skip_all)]
+    async fn create(&self, token: RefreshToken) -> Result<RefreshToken, AuthError> {
+        let mut connection = self.connection().await?;
+        let query = r#"
+            INSERT INTO refresh_tokens (
+                id, user_id, revoked, expires_at, created_at
+            ) VALUES (
+                $1, $2, $3, $4, $5
+            )
+            RETURNING id, user_id, revoked, expires_at, created_at
+        "#;
+
+        let model = sqlx::query_as::<_, RefreshTokenModel>(query)
+            .bind(token.id)
+            .bind(token.user_id)
+            .bind(token.revoked)
+            .bind(token.expires_at)
+            .bind(token.created_at)
+            .fetch_one(&mut *connection)
+            .await
+            .map_err(map_db_error)?;
+
+        Ok(RefreshToken::from(model))
+    }
+
+    #[instrument(name = "RefreshTokenRepository::revoke", skip_all)]
+    async fn revoke(&self, id: Uuid) -> Result<bool, AuthError> {
+        let mut connection = self.connection().await?;
+        let query = r#"
+            UPDATE refresh_tokens SET revoked = TRUE
+            WHERE id = $1 AND revoked = FALSE
+        "#;
+
+        let result = sqlx::query(query)
+            .bind(id)
+            .execute(&mut *connection)
+            .await
+            .map_err(map_db_error)?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    #[instrument(name = "RefreshTokenRepository::revoke_all_by_user", skip_all)]
+    async fn revoke_all_by_user(&self, user_id: Uuid) -> Result<(), AuthError> {
+        let mut connection = self.connection().await?;
+        let query = r#"
+            UPDATE refresh_tokens SET revoked = TRUE
+            WHERE user_id = $1 AND revoked = FALSE
+        "#;
+
+        sqlx::query(query)
+            .bind(user_id)
+            .execute(&mut *connection)
+            .await
+            .map_err(map_db_error)?;
+
+        Ok(())
+    }
+}
+
+#[derive(Component)]
+#[shaku(interface = PasswordResetTokenRepository)]
+pub struct PostgresPasswordResetTokenRepository {
+    #[shaku(inject)]
+    database_connection: Arc<dyn DatabaseConnection>,
+    #[shaku(default)]
+    transaction: Option<PgTransaction>,
+}
+
+impl PostgresPasswordResetTokenRepository {
+    pub fn in_transaction(
+        database_connection: Arc<dyn DatabaseConnection>,
+        transaction: PgTransaction,
+    ) -> Self {
+        Self {
+            database_connection,
+            transaction: Some(transaction),
+        }
+    }
+
+    async fn connection(&self) -> Result<ScopedConnection, AuthError> {
+        scoped_connection(
+            self.database_connection.as_ref(),
+            self.transaction.as_ref(),
+        )
+        .await
+        .map_err(map_db_error)
+    }
+}
+
+#[async_trait]
+impl PasswordResetTokenRepository for PostgresPasswordResetTokenRepository {
+    #[instrument(name = "PasswordResetTokenRepository::find_by_hash", skip_all)]
+    async fn find_by_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<PasswordResetToken>, AuthError> {
+        let mut connection = self.connection().await?;
+        let query = r#"SELECT * FROM password_reset_tokens WHERE token_hash = $1"#;
+
+        let token = sqlx::query_as::<_, PasswordResetTokenModel>(query)
+            .bind(token_hash)
+            .fetch_optional(&mut *connection)
+            .await
+            .map_err(map_db_error)?;
+
+        Ok(token.map(PasswordResetToken::from))
+    }
+
+    #[instrument(name = "PasswordResetTokenRepository::create", skip_all)]
+    async fn create(
+        &self,
+        token: PasswordResetToken,
+    ) -> Result<PasswordResetToken, AuthError> {
+        let mut connection = self.connection().await?;
+        let query = r#"
+            INSERT INTO password_reset_tokens (
+                id, user_id, token_hash, expires_at, used_at, created_at
+            ) VALUES (
+                $1, $2, $3, $4, $5, $6
+            )
+            RETURNING id, user_id, token_hash, expires_at, used_at, created_at
+        "#;
+
+        let model = sqlx::query_as::<_, PasswordResetTokenModel>(query)
+            .bind(token.id)
+            .bind(token.user_id)
+            .bind(token.token_hash)
+            .bind(token.expires_at)
+            .bind(token.used_at)
+            .bind(token.created_at)
+            .fetch_one(&mut *connection)
+            .await
+            .map_err(map_db_error)?;
+
+        Ok(PasswordResetToken::from(model))
+    }
+
+    #[instrument(name = "PasswordResetTokenRepository::mark_used", skip_all)]
+    async fn mark_used(&self, id: Uuid) -> Result<bool, AuthError> {
+        let mut connection = self.connection().await?;
+        let query = r#"
+            UPDATE password_reset_tokens SET used_at = now()
+            WHERE id = $1 AND used_at IS NULL
+        "#;
+
+        let result = sqlx::query(query)
+            .bind(id)
+            .execute(&mut *connection)
+            .await
+            .map_err(map_db_error)?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    #[instrument(name = "PasswordResetTokenRepository::delete_by_user", skip_all)]
+    async fn delete_by_user(&self, user_id: Uuid) -> Result<(), AuthError> {
+        let mut connection = self.connection().await?;
+
+        sqlx::query("DELETE FROM password_reset_tokens WHERE user_id = $1")
+            .bind(user_id)
+            .execute(&mut *connection)
+            .await
+            .map_err(map_db_error)?;
+
+        Ok(())
+    }
+}
+
+// Every failure keeps the `sqlx::Error` as its cause
+
+fn map_db_error(error: sqlx::Error) -> AuthError {
+    if is_unavailable(&error) {
+        AuthError::DatabaseUnavailable(error.into())
+    } else {
+        AuthError::unexpected(error)
+    }
+}
@@ -0,0 +1,13 @@
+use axum::routing::{post, Router};
+
+use super::controllers::*;
+use crate::shared::infrastructure::AppState;
+
+pub fn router(state: AppState) -> Router {
+    Router::new()
+        .route("/auth/login", post(login))
+        .route("/auth/refresh", post(refresh))
+        .route("/auth/password/forgot", post(forgot_password))
+        .route("/auth/password/reset", post(reset_password))
+        .with_state(state)
+}
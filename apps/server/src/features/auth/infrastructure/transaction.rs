@@ -0,0 +1,98 @@
+use async_trait::async_trait;
+use shaku::Component;
+use std::sync::Arc;
+
+use super::repository::{
+    PostgresPasswordResetTokenRepository, PostgresRefreshTokenRepository,
+};
+use crate::features::{
+    auth::domain::{
+        AuthTransaction, AuthUnitOfWork, PasswordResetTokenRepository,
+        RefreshTokenRepository,
+    },
+    user::{domain::UserRepository, infrastructure::PostgresUserRepository},
+};
+use crate::shared::{
+    domain::{Transaction, TransactionError},
+    infrastructure::{DatabaseConnection, PgTransaction},
+};
+
+#[derive(Component)]
+#[shaku(interface = AuthUnitOfWork)]
+pub struct PostgresAuthUnitOfWork {
+    #[shaku(inject)]
+    database_connection: Arc<dyn DatabaseConnection>,
+}
+
+#[async_trait]
+impl AuthUnitOfWork for PostgresAuthUnitOfWork {
+    async fn begin(&self) -> Result<Box<dyn AuthTransaction>, TransactionError> {
+        let database = &self.database_connection;
+        let transaction = PgTransaction::begin(database.get_pool()).await?;
+
+        Ok(Box::new(PostgresAuthTransaction {
+            users: PostgresUserRepository::in_transaction(
+                database.clone(),
+                transaction.clone(),
+            ),
+            refresh_tokens: PostgresRefreshTokenRepository::in_transaction(
+                database.clone(),
+                transaction.clone(),
+            ),
+            password_reset_tokens:
+                PostgresPasswordResetTokenRepository::in_transaction(
+                    database.clone(),
+                    transaction.clone(),
+                ),
+            transaction,
+        }))
+    }
+}
+
+struct PostgresAuthTransaction {
+    transaction: PgTransaction,
+    users: PostgresUserRepository,
+    refresh_tokens: PostgresRefreshTokenRepository,
+    password_reset_tokens: PostgresPasswordResetTokenRepository,
+}
+
+impl PostgresAuthTransaction {
+    // Drops the repositories, so the transaction can be finished
+
+    fn finish(self) -> PgTransaction {
+        let Self {
+            transaction,
+            users,
+            refresh_tokens,
+            password_reset_tokens,
+        } = self;
+        drop((users, refresh_tokens, password_reset_tokens));
+
+        transaction
+    }
+}
+
+#[async_trait]
+impl Transaction for PostgresAuthTransaction {
+    async fn commit(self: Box<Self>) -> Result<(), TransactionError> {
+        self.finish().commit().await
+    }
+
+    async fn rollback(self: Box<Self>) -> Result<(), TransactionError> {
+        self.finish().rollback().await
+    }
+}
+
+impl AuthTransaction for PostgresAuthTransaction {
+    fn users(&self) -> &dyn UserRepository {
+        &self.users
+    }
+
+    fn refresh_tokens(&self) -> &dyn RefreshTokenRepository {
+        &self.refresh_tokens
+    }
+
+    fn password_reset_tokens(&self) -> &dyn PasswordResetTokenRepository {
+        &self.password_reset_tokens
+    }
+}
@@ -0,0 +1,15 @@
+// This module defines the GetRolesCase Trait/Interface and its
+// corresponding return type.
+
+use async_trait::async_trait;
+use shaku::Interface;
+
+use crate::features::role::domain::{Role, RoleError};
+
+// The implementation of the GetRolesCase trait
+// is in: /features/role/application/usecases/get.rs
+
+#[async_trait]
+pub trait GetRolesCase: Interface {
+    async fn execute(&self) -> Result<Vec<Role>, RoleError>;
+}
@@ -0,0 +1,22 @@
+// This module defines the GrantRoleCase Trait/Interface and its
+// corresponding Input format.
+
+use async_trait::async_trait;
+use shaku::Interface;
+
+use crate::features::role::domain::RoleError;
+
+// This input DTO represents the role to be granted to an user.
+
+pub struct RoleAssignmentInput {
+    pub user_id: String,
+    pub role: String,
+}
+
+// Use case definition for granting a role to an existing user.
+// implementation in: /features/role/application/usecases/grant.rs
+
+#[async_trait]
+pub trait GrantRoleCase: Interface {
+    async fn execute(&self, input: RoleAssignmentInput) -> Result<(), RoleError>;
+}
@@ -0,0 +1,15 @@
+// This module defines the RevokeRoleCase Trait/Interface.
+
+use async_trait::async_trait;
+use shaku::Interface;
+
+use super::grant::RoleAssignmentInput;
+use crate::features::role::domain::RoleError;
+
+// Use case definition for revoking a role from an user.
+// implementation in: /features/role/application/usecases/revoke.rs
+
+#[async_trait]
+pub trait RevokeRoleCase: Interface {
+    async fn execute(&self, input: RoleAssignmentInput) -> Result<(), RoleError>;
+}
@@ -0,0 +1,19 @@
+pub mod interfaces {
+    mod get;
+    mod grant;
+    mod revoke;
+
+    pub use get::*;
+    pub use grant::*;
+    pub use revoke::*;
+}
+
+pub mod usecases {
+    mod get;
+    mod grant;
+    mod revoke;
+
+    pub use get::*;
+    pub use grant::*;
+    pub use revoke::*;
+}
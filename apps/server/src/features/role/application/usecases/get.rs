@@ -0,0 +1,22 @@
+use async_trait::async_trait;
+use shaku::Component;
+use std::sync::Arc;
+
+use crate::features::role::{
+    application::interfaces::GetRolesCase,
+    domain::{Role, RoleError, RoleRepository},
+};
+
+#[derive(Component)]
+#[shaku(interface = GetRolesCase)]
+pub struct GetRolesCaseImpl {
+    #[shaku(inject)]
+    repository: Arc<dyn RoleRepository>,
+}
+
+#[async_trait]
+impl GetRolesCase for GetRolesCaseImpl {
+    async fn execute(&self) -> Result<Vec<Role>, RoleError> {
+        self.repository.find_all().await
+    }
+}
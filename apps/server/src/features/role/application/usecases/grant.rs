@@ -0,0 +1,43 @@
+use async_trait::async_trait;
+use shaku::Component;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::features::{
+    role::{
+        application::interfaces::{GrantRoleCase, RoleAssignmentInput},
+        domain::{RoleError, RoleRepository},
+    },
+    user::domain::UserRepository,
+};
+
+#[derive(Component)]
+#[shaku(interface = GrantRoleCase)]
+pub struct GrantRoleCaseImpl {
+    #[shaku(inject)]
+    repository: Arc<dyn RoleRepository>,
+    #[shaku(inject)]
+    users: Arc<dyn UserRepository>,
+}
+
+#[async_trait]
+impl GrantRoleCase for GrantRoleCaseImpl {
+    async fn execute(&self, input: RoleAssignmentInput) -> Result<(), RoleError> {
+        let user_id =
+            Uuid::parse_str(&input.user_id).map_err(|_| RoleError::InvalidId)?;
+
+        if self.users.find_by_id(user_id).await?.is_none() {
+            return Err(RoleError::UserNotFound);
+        }
+
+        let Some(role) = self.repository.find_by_name(&input.role).await? else {
+            return Err(RoleError::NotFound);
+        };
+
+        if !self.repository.assign(user_id, role.id).await? {
+            return Err(RoleError::AlreadyAssigned);
+        }
+
+        Ok(())
+    }
+}
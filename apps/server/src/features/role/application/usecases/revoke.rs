@@ -0,0 +1,34 @@
+use async_trait::async_trait;
+use shaku::Component;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::features::role::{
+    application::interfaces::{RevokeRoleCase, RoleAssignmentInput},
+    domain::{RoleError, RoleRepository},
+};
+
+#[derive(Component)]
+#[shaku(interface = RevokeRoleCase)]
+pub struct RevokeRoleCaseImpl {
+    #[shaku(inject)]
+    repository: Arc<dyn RoleRepository>,
+}
+
+#[async_trait]
+impl RevokeRoleCase for RevokeRoleCaseImpl {
+    async fn execute(&self, input: RoleAssignmentInput) -> Result<(), RoleError> {
+        let user_id =
+            Uuid::parse_str(&input.user_id).map_err(|_| RoleError::InvalidId)?;
+
+        let Some(role) = self.repository.find_by_name(&input.role).await? else {
+            return Err(RoleError::NotFound);
+        };
+
+        if !self.repository.revoke(user_id, role.id).await? {
+            return Err(RoleError::NotAssigned);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,59 @@
+use std::{error::Error, fmt};
+
+use crate::features::user::domain::UserError;
+use crate::shared::domain::BoxError;
+
+#[derive(Debug)]
+pub enum RoleError {
+    NotFound,
+    UserNotFound,
+    AlreadyAssigned,
+    NotAssigned,
+    InvalidId,
+    DatabaseUnavailable(BoxError),
+    UnexpectedError(BoxError),
+}
+
+impl RoleError {
+    pub fn unexpected(error: impl Into<BoxError>) -> Self {
+        RoleError::UnexpectedError(error.into())
+    }
+}
+
+impl fmt::Display for RoleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RoleError::NotFound => write!(f, "role not found"),
+            RoleError::UserNotFound => write!(f, "user not found"),
+            RoleError::AlreadyAssigned => write!(f, "role already assigned"),
+            RoleError::NotAssigned => write!(f, "role not assigned"),
+            RoleError::InvalidId => write!(f, "invalid user id"),
+            RoleError::DatabaseUnavailable(_) => write!(f, "database unavailable"),
+            RoleError::UnexpectedError(_) => write!(f, "unexpected error"),
+        }
+    }
+}
+
+impl Error for RoleError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            RoleError::DatabaseUnavailable(source)
+            | RoleError::UnexpectedError(source) => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+// The role use cases only look users up, any failure there keeps its
+// cause and an unreachable database is still reported as such
+
+impl From<UserError> for RoleError {
+    fn from(error: UserError) -> Self {
+        match error {
+            UserError::DatabaseUnavailable(source) => {
+                RoleError::DatabaseUnavailable(source)
+            }
+            error => RoleError::unexpected(error),
+        }
+    }
+}
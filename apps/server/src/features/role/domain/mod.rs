@@ -0,0 +1,9 @@
+mod entity;
+mod errors;
+mod repository;
+
+pub mod permissions;
+
+pub use entity::*;
+pub use errors::*;
+pub use repository::*;
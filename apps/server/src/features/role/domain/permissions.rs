@@ -0,0 +1,8 @@
+// Names of the permissions seeded by the migrations, routes and
+// controllers must reference these constants instead of raw strings.
+
+pub const USERS_LIST: &str = "users:list";
+pub const USERS_UPDATE: &str = "users:update";
+pub const USERS_DELETE: &str = "users:delete";
+pub const USERS_PURGE: &str = "users:purge";
+pub const ROLES_MANAGE: &str = "roles:manage";
@@ -0,0 +1,17 @@
+use async_trait::async_trait;
+use shaku::Interface;
+use uuid::Uuid;
+
+use super::{entity::Role, errors::RoleError};
+
+// `assign` and `revoke` return whether the user roles actually changed,
+// so use cases can report duplicated or missing assignments.
+
+#[async_trait]
+pub trait RoleRepository: Interface {
+    async fn find_all(&self) -> Result<Vec<Role>, RoleError>;
+    async fn find_by_name(&self, name: &str) -> Result<Option<Role>, RoleError>;
+    async fn find_by_user(&self, user_id: Uuid) -> Result<Vec<Role>, RoleError>;
+    async fn assign(&self, user_id: Uuid, role_id: Uuid) -> Result<bool, RoleError>;
+    async fn revoke(&self, user_id: Uuid, role_id: Uuid) -> Result<bool, RoleError>;
+}
@@ -0,0 +1,87 @@
+use axum::{extract::Path, http::StatusCode};
+use axum_responses::http::{ControllerResult, HttpResponse};
+use serde_json::json;
+
+use crate::{
+    features::role::{
+        application::interfaces::{
+            GetRolesCase, GrantRoleCase, RevokeRoleCase, RoleAssignmentInput,
+        },
+        infrastructure::{
+            dtos::GrantRoleDto, models::RoleResponseDTO, openapi::RolesBody,
+        },
+    },
+    shared::infrastructure::{
+        extractors::{BodyValidator, UseCase},
+        openapi::MessageResponse,
+    },
+};
+
+#[utoipa::path(
+    get,
+    path = "/roles",
+    tag = "roles",
+    security(("bearerAuth" = [])),
+    responses((status = 200, description = "Every role and its permissions", body = RolesBody))
+)]
+pub async fn get_roles(use_case: UseCase<dyn GetRolesCase>) -> ControllerResult {
+    let data = use_case.run(|use_case| use_case.execute()).await?;
+    let roles: Vec<RoleResponseDTO> =
+        data.into_iter().map(RoleResponseDTO::from).collect();
+
+    HttpResponse::build()
+        .code(200)
+        .body(json!({ "data": roles }))
+        .wrap()
+}
+
+#[utoipa::path(
+    post,
+    path = "/users/{id}/roles",
+    tag = "roles",
+    params(("id" = Uuid, Path, description = "Id of the user")),
+    request_body = GrantRoleDto,
+    security(("bearerAuth" = [])),
+    responses((status = 201, description = "The role was granted", body = MessageResponse))
+)]
+pub async fn grant_role(
+    use_case: UseCase<dyn GrantRoleCase>,
+    Path(user_id): Path<String>,
+    BodyValidator(dto): BodyValidator<GrantRoleDto>,
+) -> ControllerResult {
+    use_case
+        .run(|use_case| {
+            use_case.execute(RoleAssignmentInput {
+                user_id,
+                role: dto.role,
+            })
+        })
+        .await?;
+
+    HttpResponse::build()
+        .status(StatusCode::CREATED)
+        .body(json!({ "message": "Role granted successfully" }))
+        .wrap()
+}
+
+#[utoipa::path(
+    delete,
+    path = "/users/{id}/roles/{role}",
+    tag = "roles",
+    params(("id" = Uuid, Path, description = "Id of the user"), ("role" = String, Path, description = "Name of the role")),
+    security(("bearerAuth" = [])),
+    responses((status = 200, description = "The role was revoked", body = MessageResponse))
+)]
+pub async fn revoke_role(
+    use_case: UseCase<dyn RevokeRoleCase>,
+    Path((user_id, role)): Path<(String, String)>,
+) -> ControllerResult {
+    use_case
+        .run(|use_case| use_case.execute(RoleAssignmentInput { user_id, role }))
+        .await?;
+
+    HttpResponse::build()
+        .status(StatusCode::OK)
+        .body(json!({ "message": "Role revoked successfully" }))
+        .wrap()
+}
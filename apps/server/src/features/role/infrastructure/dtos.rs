@@ -0,0 +1,13 @@
+// This module contains the data transfer objects (DTOs) for the role endpoints.
+
+use serde::Deserialize;
+use utoipa::ToSchema;
+use validator::Validate;
+
+#[derive(Deserialize, Validate, ToSchema)]
+pub struct GrantRoleDto {
+    /// Name of the role, e.g. `admin`
+    #[validate(length(min = 1, max = 50))]
+    #[schema(min_length = 1, max_length = 50)]
+    pub role: String,
+}
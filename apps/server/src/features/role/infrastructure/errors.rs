@@ -0,0 +1,65 @@
+// This file implements the conversion from the `RoleError` enum
+// to the `HttpResponse` type, an `application/problem+json` document
+// (see `shared::infrastructure::problem`). The messages of each code
+// live in the catalogs, `locales/<lang>.toml`.
+
+use axum::http::StatusCode;
+use axum_responses::http::HttpResponse;
+
+use crate::features::role::domain::RoleError;
+use crate::shared::{domain::error_chain, infrastructure::problem::Problem};
+
+// Documented as the error responses of the role routes (`openapi.rs`)
+
+impl From<&RoleError> for Problem {
+    fn from(value: &RoleError) -> Self {
+        match value {
+            RoleError::NotFound => {
+                Problem::new(StatusCode::NOT_FOUND, "role.not_found").field("role")
+            }
+
+            RoleError::UserNotFound => {
+                Problem::new(StatusCode::NOT_FOUND, "role.user_not_found")
+            }
+
+            RoleError::AlreadyAssigned => {
+                Problem::new(StatusCode::CONFLICT, "role.already_assigned")
+                    .field("role")
+            }
+
+            RoleError::NotAssigned => {
+                Problem::new(StatusCode::NOT_FOUND, "role.not_assigned")
+                    .field("role")
+            }
+
+            RoleError::InvalidId => {
+                Problem::new(StatusCode::BAD_REQUEST, "role.invalid_id").field("id")
+            }
+
+            RoleError::DatabaseUnavailable(_) => Problem::new(
+                StatusCode::SERVICE_UNAVAILABLE,
+                "server.database_unavailable",
+            ),
+
+            RoleError::UnexpectedError(_) => Problem::new(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "server.unexpected_error",
+            ),
+        }
+    }
+}
+
+// The client only gets the generic problem, the cause is logged
+
+impl From<RoleError> for HttpResponse {
+    fn from(value: RoleError) -> Self {
+        if matches!(
+            value,
+            RoleError::DatabaseUnavailable(_) | RoleError::UnexpectedError(_)
+        ) {
+            tracing::error!(error = %error_chain(&value), "role operation failed");
+        }
+
+        Problem::from(&value).into()
+    }
+}
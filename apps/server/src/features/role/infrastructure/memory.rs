@@ -0,0 +1,77 @@
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::features::role::domain::{Role, RoleError, RoleRepository};
+
+// Roles and their assignments kept in memory, for the tests (see
+// `TestModuleBuilder`). It starts without roles, unlike the database
+// seeded by the roles migration. Clones share the same storage.
+
+#[derive(Clone, Default)]
+pub struct InMemoryRoleRepository {
+    state: Arc<Mutex<RoleState>>,
+}
+
+#[derive(Default)]
+struct RoleState {
+    roles: Vec<Role>,
+    // (user_id, role_id)
+    assignments: Vec<(Uuid, Uuid)>,
+}
+
+impl InMemoryRoleRepository {
+    fn state(&self) -> MutexGuard<'_, RoleState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[async_trait]
+impl RoleRepository for InMemoryRoleRepository {
+    async fn find_all(&self) -> Result<Vec<Role>, RoleError> {
+        Ok(self.state().roles.clone())
+    }
+
+    async fn find_by_name(&self, name: &str) -> Result<Option<Role>, RoleError> {
+        Ok(self
+            .state()
+            .roles
+            .iter()
+            .find(|role| role.name == name)
+            .cloned())
+    }
+
+    async fn find_by_user(&self, user_id: Uuid) -> Result<Vec<Role>, RoleError> {
+        let state = self.state();
+
+        Ok(state
+            .roles
+            .iter()
+            .filter(|role| state.assignments.contains(&(user_id, role.id)))
+            .cloned()
+            .collect())
+    }
+
+    async fn assign(&self, user_id: Uuid, role_id: Uuid) -> Result<bool, RoleError> {
+        let mut state = self.state();
+
+        if state.assignments.contains(&(user_id, role_id)) {
+            return Ok(false);
+        }
+
+        state.assignments.push((user_id, role_id));
+        Ok(true)
+    }
+
+    async fn revoke(&self, user_id: Uuid, role_id: Uuid) -> Result<bool, RoleError> {
+        let mut state = self.state();
+        let before = state.assignments.len();
+
+        state
+            .assignments
+            .retain(|assignment| *assignment != (user_id, role_id));
+
+        Ok(state.assignments.len() < before)
+    }
+}
@@ -0,0 +1,15 @@
+mod controllers;
+mod dtos;
+mod errors;
+#[cfg(test)]
+mod memory;
+mod models;
+mod openapi;
+mod repository;
+mod routes;
+
+#[cfg(test)]
+pub use memory::*;
+pub use openapi::RoleApi;
+pub use repository::*;
+pub use routes::router as role_router;
@@ -0,0 +1,52 @@
+// This module contains the RoleModel struct and its conversions traits.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::prelude::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::features::role::domain::Role;
+
+// The permissions of a role are aggregated in the query itself,
+// see `PostgresRoleRepository` for the `array_agg` projection.
+
+#[derive(FromRow, Debug, Clone)]
+pub struct RoleModel {
+    pub id: Uuid,
+    pub name: String,
+    pub permissions: Vec<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<RoleModel> for Role {
+    fn from(model: RoleModel) -> Self {
+        Role {
+            id: model.id,
+            name: model.name,
+            permissions: model.permissions,
+            created_at: model.created_at,
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[schema(as = Role)]
+pub struct RoleResponseDTO {
+    pub id: Uuid,
+    pub name: String,
+    pub permissions: Vec<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<Role> for RoleResponseDTO {
+    fn from(role: Role) -> Self {
+        RoleResponseDTO {
+            id: role.id,
+            name: role.name,
+            permissions: role.permissions,
+            created_at: role.created_at,
+        }
+    }
+}
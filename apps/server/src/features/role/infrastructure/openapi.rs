@@ -0,0 +1,99 @@
+// OpenAPI documentation of the role routes, merged into the specification
+// by `shared::infrastructure::openapi`.
+
+use utoipa::{
+    openapi::{path::HttpMethod, OpenApi as Spec},
+    Modify, OpenApi, ToSchema,
+};
+
+use super::controllers::*;
+use super::dtos::GrantRoleDto;
+use super::models::RoleResponseDTO;
+use crate::features::{
+    auth::infrastructure::{authentication_problems, permission_problems},
+    role::domain::RoleError,
+};
+use crate::shared::infrastructure::{
+    openapi::{body_problems, document_problems},
+    problem::Problem,
+};
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(get_roles, grant_role, revoke_role),
+    components(schemas(GrantRoleDto, RoleResponseDTO)),
+    tags((
+        name = "roles",
+        description = "Role administration, requires the `roles:manage` permission"
+    )),
+    modifiers(&RoleProblems)
+)]
+pub struct RoleApi;
+
+#[allow(dead_code)]
+#[derive(ToSchema)]
+pub struct RolesBody {
+    data: Vec<RoleResponseDTO>,
+}
+
+struct RoleProblems;
+
+impl Modify for RoleProblems {
+    fn modify(&self, spec: &mut Spec) {
+        use RoleError::*;
+
+        document_problems(spec, "/roles", HttpMethod::Get, problems([]));
+
+        document_problems(
+            spec,
+            "/users/{id}/roles",
+            HttpMethod::Post,
+            [
+                body_problems(),
+                problems([InvalidId, NotFound, UserNotFound, AlreadyAssigned]),
+            ]
+            .concat(),
+        );
+
+        document_problems(
+            spec,
+            "/users/{id}/roles/{role}",
+            HttpMethod::Delete,
+            problems([InvalidId, NotFound, NotAssigned]),
+        );
+    }
+}
+
+// Every role route is guarded by `require_permission(ROLES_MANAGE)` and
+// reaches the database
+
+fn problems(errors: impl IntoIterator<Item = RoleError>) -> Vec<Problem> {
+    let errors = errors
+        .into_iter()
+        .chain([
+            RoleError::DatabaseUnavailable("".into()),
+            RoleError::unexpected(""),
+        ])
+        .map(|error| Problem::from(&error))
+        .collect();
+
+    [authentication_problems(), permission_problems(), errors].concat()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::shared::infrastructure::testing::{
+        assert_documented_bounds, schema_properties,
+    };
+
+    #[test]
+    fn documented_bounds_match_the_validation() {
+        assert_documented_bounds::<GrantRoleDto>(
+            &schema_properties("GrantRoleDto"),
+            json!({ "role": "" }),
+        );
+    }
+}
@@ -0,0 +1,144 @@
+use async_trait::async_trait;
+use shaku::Component;
+use std::sync::Arc;
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::shared::infrastructure::{
+    is_unavailable, scoped_connection, DatabaseConnection,
+};
+
+use crate::features::role::{
+    domain::{Role, RoleError, RoleRepository},
+    infrastructure::models::RoleModel,
+};
+
+// Every role query projects the role columns plus the names of its
+// permissions, so they can be mapped straight into a `RoleModel`.
+
+const SELECT_ROLES: &str = r#"
+    SELECT
+        r.id, r.name, r.created_at,
+        COALESCE(
+            array_agg(p.name ORDER BY p.name) FILTER (WHERE p.name IS NOT NULL),
+            '{}'
+        ) AS permissions
+    FROM roles r
+    LEFT JOIN role_permissions rp ON rp.role_id = r.id
+    LEFT JOIN permissions p ON p.id = rp.permission_id
+"#;
+
+#[derive(Component)]
+#[shaku(interface = RoleRepository)]
+pub struct PostgresRoleRepository {
+    #[shaku(inject)]
+    database_connection: Arc<dyn DatabaseConnection>,
+}
+
+#[async_trait]
+impl RoleRepository for PostgresRoleRepository {
+    #[instrument(name = "RoleRepository::find_all", skip_all)]
+    async fn find_all(&self) -> Result<Vec<Role>, RoleError> {
+        let mut connection =
+            scoped_connection(self.database_connection.as_ref(), None)
+                .await
+                .map_err(map_db_error)?;
+        let query = format!("{SELECT_ROLES} GROUP BY r.id ORDER BY r.name");
+
+        let roles = sqlx::query_as::<_, RoleModel>(&query)
+            .fetch_all(&mut *connection)
+            .await
+            .map_err(map_db_error)?;
+
+        Ok(roles.into_iter().map(Role::from).collect())
+    }
+
+    #[instrument(name = "RoleRepository::find_by_name", skip_all)]
+    async fn find_by_name(&self, name: &str) -> Result<Option<Role>, RoleError> {
+        let mut connection =
+            scoped_connection(self.database_connection.as_ref(), None)
+                .await
+                .map_err(map_db_error)?;
+        let query = format!("{SELECT_ROLES} WHERE r.name = $1 GROUP BY r.id");
+
+        let role = sqlx::query_as::<_, RoleModel>(&query)
+            .bind(name)
+            .fetch_optional(&mut *connection)
+            .await
+            .map_err(map_db_error)?;
+
+        Ok(role.map(Role::from))
+    }
+
+    #[instrument(name = "RoleRepository::find_by_user", skip_all)]
+    async fn find_by_user(&self, user_id: Uuid) -> Result<Vec<Role>, RoleError> {
+        let mut connection =
+            scoped_connection(self.database_connection.as_ref(), None)
+                .await
+                .map_err(map_db_error)?;
+        let query = format!(
+            "{SELECT_ROLES}
+            WHERE r.id IN (SELECT role_id FROM user_roles WHERE user_id = $1)
+            GROUP BY r.id ORDER BY r.name"
+        );
+
+        let roles = sqlx::query_as::<_, RoleModel>(&query)
+            .bind(user_id)
+            .fetch_all(&mut *connection)
+            .await
+            .map_err(map_db_error)?;
+
+        Ok(roles.into_iter().map(Role::from).collect())
+    }
+
+    #[instrument(name = "RoleRepository::assign", skip_all)]
+    async fn assign(&self, user_id: Uuid, role_id: Uuid) -> Result<bool, RoleError> {
+        let mut connection =
+            scoped_connection(self.database_connection.as_ref(), None)
+                .await
+                .map_err(map_db_error)?;
+        let query = r#"
+            INSERT INTO user_roles (user_id, role_id, created_at)
+            VALUES ($1, $2, now())
+            ON CONFLICT DO NOTHING
+        "#;
+
+        let result = sqlx::query(query)
+            .bind(user_id)
+            .bind(role_id)
+            .execute(&mut *connection)
+            .await
+            .map_err(map_db_error)?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    #[instrument(name = "RoleRepository::revoke", skip_all)]
+    async fn revoke(&self, user_id: Uuid, role_id: Uuid) -> Result<bool, RoleError> {
+        let mut connection =
+            scoped_connection(self.database_connection.as_ref(), None)
+                .await
+                .map_err(map_db_error)?;
+
+        let result = sqlx::query(
+            "DELETE FROM user_roles WHERE user_id = $1 AND role_id = $2",
+        )
+        .bind(user_id)
+        .bind(role_id)
+        .execute(&mut *connection)
+        .await
+        .map_err(map_db_error)?;
+
+        Ok(result.rows_affected() == 1)
+    }
+}
+
+// Every failure keeps the `sqlx::Error` as its cause
+
+fn map_db_error(error: sqlx::Error) -> RoleError {
+    if is_unavailable(&error) {
+        RoleError::DatabaseUnavailable(error.into())
+    } else {
+        RoleError::unexpected(error)
+    }
+}
@@ -0,0 +1,21 @@
+use axum::middleware::from_fn_with_state;
+use axum::routing::{delete, get, post, Router};
+
+use super::controllers::*;
+use crate::features::role::domain::permissions::ROLES_MANAGE;
+use crate::shared::infrastructure::{guards::require_permission, AppState};
+
+// Role administration is restricted to users with the `roles:manage`
+// permission, granted to the `admin` role by the roles migration.
+
+pub fn router(state: AppState) -> Router {
+    Router::new()
+        .route("/roles", get(get_roles))
+        .route("/users/{id}/roles", post(grant_role))
+        .route("/users/{id}/roles/{role}", delete(revoke_role))
+        .route_layer(from_fn_with_state(
+            state.clone(),
+            require_permission(ROLES_MANAGE),
+        ))
+        .with_state(state)
+}
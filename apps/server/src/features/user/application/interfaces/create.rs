@@ -20,7 +20,6 @@ use crate::features::user::domain::{User, UserError};
 /// Datos requeridos para crear un nuevo usuario.
 /// Este DTO representa la entrada cruda recibida desde
 /// la capa de infraestructura (por ejemplo, desde un controller).
-
 pub struct CreateUserInput {
     pub username: String,
     pub email: String,
@@ -40,7 +39,6 @@ pub trait CreateUserCase: Interface {
 /// Implementación de conversión desde la entrada del caso de uso a la
 /// entidad de dominio `User`. Se utiliza `TryFrom` porque la creación
 /// de un `User` puede fallar (por ejemplo, al encriptar la contraseña).
-
 impl From<CreateUserInput> for User {
     fn from(input: CreateUserInput) -> Self {
         let now = chrono::Utc::now();
@@ -53,6 +51,7 @@ impl From<CreateUserInput> for User {
             password: input.password,
             created_at: now,
             updated_at: now,
+            deleted_at: None,
         }
     }
 }
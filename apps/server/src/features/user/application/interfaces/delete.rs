@@ -13,3 +13,24 @@ use crate::features::user::domain::UserError;
 pub trait DeleteUserCase: Interface {
     async fn execute(&self, user_id: String) -> Result<(), UserError>;
 }
+
+#[async_trait]
+pub trait RestoreUserCase: Interface {
+    async fn execute(&self, user_id: String) -> Result<(), UserError>;
+}
+
+// Permanently removes a user, whether it was soft-deleted or not
+
+#[async_trait]
+pub trait PurgeUserCase: Interface {
+    async fn execute(&self, user_id: String) -> Result<(), UserError>;
+}
+
+// Run periodically by the retention job, hard-deletes the users that
+// were soft-deleted longer than `users.retention_period` ago and returns
+// how many were removed.
+
+#[async_trait]
+pub trait PurgeDeletedUsersCase: Interface {
+    async fn execute(&self) -> Result<u64, UserError>;
+}
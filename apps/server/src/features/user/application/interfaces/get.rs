@@ -4,12 +4,21 @@
 use async_trait::async_trait;
 use shaku::Interface;
 
-use crate::features::user::domain::{User, UserError};
+use crate::features::user::domain::{User, UserError, UserQuery};
+use crate::shared::domain::Page;
 
 // The implementation of the GetUsersCase trait
 // is in: /features/user/application/use_cases/get.rs
 
 #[async_trait]
 pub trait GetUsersCase: Interface {
-    async fn execute(&self) -> Result<Vec<User>, UserError>;
+    async fn execute(&self, query: UserQuery) -> Result<Page<User>, UserError>;
+}
+
+// The implementation of the GetUserByIdCase trait
+// is in: /features/user/application/use_cases/get.rs
+
+#[async_trait]
+pub trait GetUserByIdCase: Interface {
+    async fn execute(&self, user_id: String) -> Result<User, UserError>;
 }
@@ -0,0 +1,25 @@
+// This module defines the VerifyEmailCase and ResendVerificationCase
+// Traits/Interfaces.
+
+use async_trait::async_trait;
+use shaku::Interface;
+
+use crate::features::user::domain::{User, UserError};
+
+// Use case definition for verifying the email of an user with the
+// token received by email. implementation in:
+// /features/user/application/usecases/verify.rs
+
+#[async_trait]
+pub trait VerifyEmailCase: Interface {
+    async fn execute(&self, token: String) -> Result<User, UserError>;
+}
+
+// Use case definition for sending a new verification link. It succeeds
+// silently for unknown or already verified emails, so it can't be used
+// to find out which emails are registered.
+
+#[async_trait]
+pub trait ResendVerificationCase: Interface {
+    async fn execute(&self, email: String) -> Result<(), UserError>;
+}
@@ -3,16 +3,21 @@ pub mod interfaces {
     mod delete;
     mod get;
     mod update;
+    mod verify;
 
     pub use create::*;
     pub use delete::*;
     pub use get::*;
     pub use update::*;
+    pub use verify::*;
 }
 
 pub mod services {
     mod password;
+    mod verification;
+
     pub use password::*;
+    pub use verification::*;
 }
 
 pub mod usecases {
@@ -20,9 +25,11 @@ pub mod usecases {
     mod delete;
     mod get;
     mod update;
+    mod verify;
 
     pub use create::*;
     pub use delete::*;
     pub use get::*;
     pub use update::*;
+    pub use verify::*;
 }
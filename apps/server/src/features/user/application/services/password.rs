@@ -1,20 +1,60 @@
 use shaku::{Component, Interface};
+use std::sync::Arc;
 
 use crate::features::user::domain::UserError;
-
-const PASSWORD_HASH_COST: u32 = 10;
+use crate::shared::settings::SettingsProvider;
 
 pub trait PasswordHasher: Interface {
     fn hash(&self, password: &str) -> Result<String, UserError>;
+    fn verify(&self, password: &str, hash: &str) -> Result<bool, UserError>;
 }
 
 #[derive(Component)]
 #[shaku(interface = PasswordHasher)]
-pub struct BcryptPasswordHasher;
+pub struct BcryptPasswordHasher {
+    #[shaku(inject)]
+    settings: Arc<dyn SettingsProvider>,
+}
 
 impl PasswordHasher for BcryptPasswordHasher {
     fn hash(&self, password: &str) -> Result<String, UserError> {
-        bcrypt::hash(password, PASSWORD_HASH_COST)
-            .map_err(|_| UserError::UnexpectedError)
+        bcrypt::hash(password, self.settings.get().auth.password_hash_cost)
+            .map_err(UserError::unexpected)
+    }
+
+    fn verify(&self, password: &str, hash: &str) -> Result<bool, UserError> {
+        bcrypt::verify(password, hash).map_err(UserError::unexpected)
+    }
+}
+
+// Stand-in for tests, bcrypt is slow on purpose. The "hash" is the
+// password with a prefix, so it is never equal to the password itself.
+// Clones share the count of verifications, which timing-sensitive paths
+// are asserted on.
+
+#[cfg(test)]
+#[derive(Clone, Default)]
+pub struct FakePasswordHasher {
+    verifications: Arc<std::sync::atomic::AtomicUsize>,
+}
+
+#[cfg(test)]
+impl FakePasswordHasher {
+    pub fn verifications(&self) -> usize {
+        self.verifications.load(std::sync::atomic::Ordering::SeqCst)
+    }
+}
+
+#[cfg(test)]
+impl PasswordHasher for FakePasswordHasher {
+    fn hash(&self, password: &str) -> Result<String, UserError> {
+        Ok(format!("fake-hash:{password}"))
+    }
+
+    fn verify(&self, password: &str, hash: &str) -> Result<bool, UserError> {
+        self.verifications
+            .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+
+        Ok(hash.strip_prefix("fake-hash:") == Some(password))
     }
 }
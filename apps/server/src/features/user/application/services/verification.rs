@@ -0,0 +1,69 @@
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use shaku::{Component, Interface};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::features::user::domain::{
+    User, UserError, VerificationToken, VerificationTokenRepository,
+};
+use crate::shared::{
+    application::{generate_secure_token, hash_token, Email, Mailer},
+    settings::SettingsProvider,
+};
+
+// Issues a new verification token for a user and emails the link to
+// `GET /users/verify?token=`, previous tokens of the user are discarded.
+
+#[async_trait]
+pub trait VerificationService: Interface {
+    async fn send_verification(&self, user: &User) -> Result<(), UserError>;
+}
+
+#[derive(Component)]
+#[shaku(interface = VerificationService)]
+pub struct VerificationServiceImpl {
+    #[shaku(inject)]
+    repository: Arc<dyn VerificationTokenRepository>,
+    #[shaku(inject)]
+    mailer: Arc<dyn Mailer>,
+    #[shaku(inject)]
+    settings: Arc<dyn SettingsProvider>,
+}
+
+#[async_trait]
+impl VerificationService for VerificationServiceImpl {
+    async fn send_verification(&self, user: &User) -> Result<(), UserError> {
+        let settings = self.settings.get();
+        let token = generate_secure_token();
+        let now = Utc::now();
+
+        self.repository.delete_by_user(user.id).await?;
+        self.repository
+            .create(VerificationToken {
+                id: Uuid::new_v4(),
+                user_id: user.id,
+                token_hash: hash_token(&token),
+                expires_at: now
+                    + Duration::seconds(settings.auth.email_verification_token_ttl),
+                used_at: None,
+                created_at: now,
+            })
+            .await?;
+
+        let link = format!("{}/users/verify?token={token}", settings.public.api_url);
+
+        self.mailer
+            .send(Email {
+                to: user.email.clone(),
+                subject: "Verify your email address".to_string(),
+                body: format!(
+                    "Hi {},\n\nPlease verify your email address by opening \
+                     the following link:\n\n{link}\n",
+                    user.username
+                ),
+            })
+            .await
+            .map_err(UserError::unexpected)
+    }
+}
@@ -5,10 +5,11 @@ use std::sync::Arc;
 use crate::features::user::{
     application::{
         interfaces::{CreateUserCase, CreateUserInput},
-        services::PasswordHasher,
+        services::{PasswordHasher, VerificationService},
     },
     domain::{User, UserError, UserRepository},
 };
+use crate::shared::domain::error_chain;
 
 #[derive(Component)]
 #[shaku(interface = CreateUserCase)]
@@ -17,6 +18,8 @@ pub struct CreateUserCaseImpl {
     repository: Arc<dyn UserRepository>,
     #[shaku(inject)]
     hasher: Arc<dyn PasswordHasher>,
+    #[shaku(inject)]
+    verification: Arc<dyn VerificationService>,
 }
 
 #[async_trait]
@@ -25,32 +28,79 @@ impl CreateUserCase for CreateUserCaseImpl {
         // Convert the input dto format to the domain entity
         let mut user = User::from(input);
 
-        // Check if the user already exists by username or email
-        // in database, this tasks is done in parallel
+        // check disposable/throwaway email
+
+        if !mailchecker::is_valid(&user.email) {
+            return Err(UserError::InvalidEmail);
+        }
+
+        user.password = self.hasher.hash(&user.password)?;
 
-        let (username, email) = tokio::try_join!(
-            self.repository.find_by_username(&user.username),
-            self.repository.find_by_email(&user.email)
-        )?;
+        // The unique constraints reject a taken username or email, even
+        // against concurrent requests, and the repository maps them to
+        // `UsernameAlreadyExists`/`EmailAlreadyExists`
 
-        // Then check if the user already exists synchronously
+        let user = self.repository.create(user).await?;
 
-        if username.is_some() {
-            return Err(UserError::UsernameAlreadyExists);
-        }
+        // The account already exists at this point, if the email can't be
+        // sent the user can still ask for a new one at /users/verify/resend
 
-        if email.is_some() {
-            return Err(UserError::EmailAlreadyExists);
+        if let Err(error) = self.verification.send_verification(&user).await {
+            tracing::error!(
+                error = %error_chain(&error),
+                "failed to send the verification email"
+            );
         }
 
-        // check disposable/throwaway email
+        Ok(user)
+    }
+}
 
-        if !mailchecker::is_valid(&user.email) {
-            return Err(UserError::InvalidEmail);
+#[cfg(test)]
+mod tests {
+    use shaku::HasComponent;
+
+    use super::*;
+    use crate::shared::infrastructure::testing::TestModuleBuilder;
+
+    fn input(email: &str) -> CreateUserInput {
+        CreateUserInput {
+            username: "alice".to_string(),
+            email: email.to_string(),
+            password: "Secret123!".to_string(),
         }
+    }
 
-        user.password = self.hasher.hash(&user.password)?;
+    #[tokio::test]
+    async fn hashes_the_password_and_sends_the_verification_email() {
+        let app = TestModuleBuilder::new()
+            .settings(|settings| {
+                settings.public.api_url = "https://api.example.com".to_string()
+            })
+            .build();
+        let use_case: &dyn CreateUserCase = app.module().resolve_ref();
+
+        let user = use_case.execute(input("alice@example.com")).await.unwrap();
+        let sent = app.mailer.sent();
+
+        assert_ne!(user.password, "Secret123!");
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].to, "alice@example.com");
+        assert!(sent[0]
+            .body
+            .contains("https://api.example.com/users/verify?token="));
+    }
+
+    #[tokio::test]
+    async fn rejects_disposable_emails() {
+        let app = TestModuleBuilder::new().build();
+        let use_case: &dyn CreateUserCase = app.module().resolve_ref();
+
+        let result = use_case.execute(input("alice@mailinator.com")).await;
+        let stored = app.users.find_by_email("alice@mailinator.com").await;
 
-        self.repository.create(user).await
+        assert!(matches!(result, Err(UserError::InvalidEmail)));
+        assert!(stored.unwrap().is_none());
+        assert!(app.mailer.sent().is_empty());
     }
 }
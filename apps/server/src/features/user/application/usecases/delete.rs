@@ -1,12 +1,20 @@
 use async_trait::async_trait;
+use chrono::{Duration, Utc};
 use shaku::Component;
 use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::features::user::{
-    application::interfaces::DeleteUserCase,
+    application::interfaces::{
+        DeleteUserCase, PurgeDeletedUsersCase, PurgeUserCase, RestoreUserCase,
+    },
     domain::{UserError, UserRepository},
 };
+use crate::shared::settings::SettingsProvider;
+
+// Deleting a user only marks it as deleted, it can be restored until
+// an admin purges it or the retention job removes it. The sessions of
+// the user are not revoked, but they can no longer be refreshed.
 
 #[derive(Component)]
 #[shaku(interface = DeleteUserCase)]
@@ -21,10 +29,70 @@ impl DeleteUserCase for DeleteUserCaseImpl {
         let parsed_user_id =
             Uuid::parse_str(&id).map_err(|_| UserError::InvalidId)?;
 
-        if self.repository.find_by_id(parsed_user_id).await?.is_none() {
-            return Err(UserError::NotFound)?;
+        if !self.repository.soft_delete(parsed_user_id).await? {
+            return Err(UserError::NotFound);
         }
 
-        self.repository.delete(parsed_user_id).await
+        Ok(())
+    }
+}
+
+#[derive(Component)]
+#[shaku(interface = RestoreUserCase)]
+pub struct RestoreUserCaseImpl {
+    #[shaku(inject)]
+    repository: Arc<dyn UserRepository>,
+}
+
+#[async_trait]
+impl RestoreUserCase for RestoreUserCaseImpl {
+    async fn execute(&self, id: String) -> Result<(), UserError> {
+        let parsed_user_id =
+            Uuid::parse_str(&id).map_err(|_| UserError::InvalidId)?;
+
+        if !self.repository.restore(parsed_user_id).await? {
+            return Err(UserError::NotFound);
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Component)]
+#[shaku(interface = PurgeUserCase)]
+pub struct PurgeUserCaseImpl {
+    #[shaku(inject)]
+    repository: Arc<dyn UserRepository>,
+}
+
+#[async_trait]
+impl PurgeUserCase for PurgeUserCaseImpl {
+    async fn execute(&self, id: String) -> Result<(), UserError> {
+        let parsed_user_id =
+            Uuid::parse_str(&id).map_err(|_| UserError::InvalidId)?;
+
+        if !self.repository.purge(parsed_user_id).await? {
+            return Err(UserError::NotFound);
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Component)]
+#[shaku(interface = PurgeDeletedUsersCase)]
+pub struct PurgeDeletedUsersCaseImpl {
+    #[shaku(inject)]
+    repository: Arc<dyn UserRepository>,
+    #[shaku(inject)]
+    settings: Arc<dyn SettingsProvider>,
+}
+
+#[async_trait]
+impl PurgeDeletedUsersCase for PurgeDeletedUsersCaseImpl {
+    async fn execute(&self) -> Result<u64, UserError> {
+        let retention = self.settings.get().users.retention_period;
+        let cutoff = Utc::now() - Duration::seconds(retention);
+        self.repository.purge_deleted_before(cutoff).await
     }
 }
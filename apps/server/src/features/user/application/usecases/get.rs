@@ -1,11 +1,13 @@
 use async_trait::async_trait;
 use shaku::Component;
 use std::sync::Arc;
+use uuid::Uuid;
 
 use crate::features::user::{
-    application::interfaces::GetUsersCase,
-    domain::{User, UserError, UserRepository},
+    application::interfaces::{GetUserByIdCase, GetUsersCase},
+    domain::{User, UserError, UserQuery, UserRepository},
 };
+use crate::shared::domain::Page;
 
 #[derive(Component)]
 #[shaku(interface = GetUsersCase)]
@@ -16,7 +18,27 @@ pub struct GetUsersCaseImpl {
 
 #[async_trait]
 impl GetUsersCase for GetUsersCaseImpl {
-    async fn execute(&self) -> Result<Vec<User>, UserError> {
-        self.repository.find_all().await
+    async fn execute(&self, query: UserQuery) -> Result<Page<User>, UserError> {
+        self.repository.find_all(&query).await
+    }
+}
+
+#[derive(Component)]
+#[shaku(interface = GetUserByIdCase)]
+pub struct GetUserByIdCaseImpl {
+    #[shaku(inject)]
+    repository: Arc<dyn UserRepository>,
+}
+
+#[async_trait]
+impl GetUserByIdCase for GetUserByIdCaseImpl {
+    async fn execute(&self, id: String) -> Result<User, UserError> {
+        let parsed_user_id =
+            Uuid::parse_str(&id).map_err(|_| UserError::InvalidId)?;
+
+        self.repository
+            .find_by_id(parsed_user_id)
+            .await?
+            .ok_or(UserError::NotFound)
     }
 }
@@ -7,10 +7,11 @@ use uuid::Uuid;
 use crate::features::user::{
     application::{
         interfaces::{UpdateUserCase, UpdateUserInput},
-        services::PasswordHasher,
+        services::{PasswordHasher, VerificationService},
     },
     domain::{User, UserError, UserRepository},
 };
+use crate::shared::domain::error_chain;
 
 #[derive(Component)]
 #[shaku(interface = UpdateUserCase)]
@@ -19,6 +20,8 @@ pub struct UpdateUserCaseImpl {
     pub repository: Arc<dyn UserRepository>,
     #[shaku(inject)]
     pub hasher: Arc<dyn PasswordHasher>,
+    #[shaku(inject)]
+    pub verification: Arc<dyn VerificationService>,
 }
 
 #[async_trait]
@@ -38,8 +41,21 @@ impl UpdateUserCase for UpdateUserCaseImpl {
             user.username = u
         }
 
+        // A new email address has to be verified again, emails are case
+        // insensitive so only changing the casing keeps it verified. The
+        // repository compares them the way the database does (citext).
+
+        let mut email_changed = false;
+
         if let Some(e) = input.email {
-            user.email = e
+            let owner = self.repository.find_by_email(&e).await?;
+
+            if owner.is_none_or(|owner| owner.id != user.id) {
+                user.validated = false;
+                email_changed = true;
+            }
+
+            user.email = e;
         }
 
         if let Some(p) = input.password {
@@ -47,6 +63,65 @@ impl UpdateUserCase for UpdateUserCaseImpl {
         }
 
         user.updated_at = Utc::now();
-        self.repository.update(user).await
+        let user = self.repository.update(user).await?;
+
+        if email_changed {
+            if let Err(error) = self.verification.send_verification(&user).await {
+                tracing::error!(
+                    error = %error_chain(&error),
+                    "failed to send the verification email"
+                );
+            }
+        }
+
+        Ok(user)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use shaku::HasComponent;
+
+    use super::*;
+    use crate::shared::infrastructure::testing::{user, TestModuleBuilder};
+
+    fn new_email(email: &str) -> UpdateUserInput {
+        UpdateUserInput {
+            username: None,
+            email: Some(email.to_string()),
+            password: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn keeps_the_email_verified_when_only_its_case_changes() {
+        let app = TestModuleBuilder::new().build();
+        let use_case: &dyn UpdateUserCase = app.module().resolve_ref();
+        let id = app
+            .users
+            .create(User {
+                validated: true,
+                ..user("elodie", "élodie@example.com")
+            })
+            .await
+            .unwrap()
+            .id
+            .to_string();
+
+        let renamed = use_case
+            .execute(id.clone(), new_email("ÉLODIE@example.com"))
+            .await
+            .unwrap();
+
+        assert!(renamed.validated);
+        assert!(app.mailer.sent().is_empty());
+
+        let moved = use_case
+            .execute(id, new_email("elodie@example.com"))
+            .await
+            .unwrap();
+
+        assert!(!moved.validated);
+        assert_eq!(app.mailer.sent().len(), 1);
     }
 }
@@ -0,0 +1,158 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use shaku::Component;
+use std::sync::Arc;
+
+use crate::features::user::{
+    application::{
+        interfaces::{ResendVerificationCase, VerifyEmailCase},
+        services::VerificationService,
+    },
+    domain::{
+        User, UserError, UserRepository, UserUnitOfWork, VerificationTokenRepository,
+    },
+};
+use crate::shared::{application::hash_token, domain::error_chain};
+
+#[derive(Component)]
+#[shaku(interface = VerifyEmailCase)]
+pub struct VerifyEmailCaseImpl {
+    #[shaku(inject)]
+    unit_of_work: Arc<dyn UserUnitOfWork>,
+    #[shaku(inject)]
+    tokens: Arc<dyn VerificationTokenRepository>,
+}
+
+#[async_trait]
+impl VerifyEmailCase for VerifyEmailCaseImpl {
+    async fn execute(&self, token: String) -> Result<User, UserError> {
+        let Some(stored) = self.tokens.find_by_hash(&hash_token(&token)).await?
+        else {
+            return Err(UserError::InvalidVerificationToken);
+        };
+
+        if stored.used_at.is_some() || stored.expires_at <= Utc::now() {
+            return Err(UserError::InvalidVerificationToken);
+        }
+
+        // The token is consumed only if the user is updated as well. Only
+        // the flag is written, a concurrent update of the user is kept.
+
+        let tx = self.unit_of_work.begin().await?;
+
+        if !tx.verification_tokens().mark_used(stored.id).await? {
+            return Err(UserError::InvalidVerificationToken);
+        }
+
+        let user = match tx.users().mark_validated(stored.user_id).await {
+            Err(UserError::NotFound) => {
+                return Err(UserError::InvalidVerificationToken)
+            }
+            result => result?,
+        };
+
+        tx.commit().await?;
+
+        Ok(user)
+    }
+}
+
+#[derive(Component)]
+#[shaku(interface = ResendVerificationCase)]
+pub struct ResendVerificationCaseImpl {
+    #[shaku(inject)]
+    repository: Arc<dyn UserRepository>,
+    #[shaku(inject)]
+    verification: Arc<dyn VerificationService>,
+}
+
+#[async_trait]
+impl ResendVerificationCase for ResendVerificationCaseImpl {
+    async fn execute(&self, email: String) -> Result<(), UserError> {
+        let Some(user) = self.repository.find_by_email(&email).await? else {
+            return Ok(());
+        };
+
+        // A failure is only logged, answering differently would tell that
+        // the email is registered
+
+        if !user.validated {
+            if let Err(error) = self.verification.send_verification(&user).await {
+                tracing::error!(
+                    error = %error_chain(&error),
+                    "failed to resend the verification email"
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use shaku::HasComponent;
+
+    use super::*;
+    use crate::features::user::application::interfaces::{
+        CreateUserCase, CreateUserInput,
+    };
+    use crate::shared::infrastructure::testing::{TestApp, TestModuleBuilder};
+
+    // Registers a user and returns the token of its verification link
+
+    async fn register(app: &TestApp) -> (User, String) {
+        let create: &dyn CreateUserCase = app.module().resolve_ref();
+        let user = create
+            .execute(CreateUserInput {
+                username: "alice".to_string(),
+                email: "alice@example.com".to_string(),
+                password: "Secret123!".to_string(),
+            })
+            .await
+            .unwrap();
+
+        let body = &app.mailer.sent()[0].body;
+        let token = body
+            .split("token=")
+            .nth(1)
+            .and_then(|rest| rest.split_whitespace().next())
+            .unwrap()
+            .to_string();
+
+        (user, token)
+    }
+
+    #[tokio::test]
+    async fn validates_the_user_once_per_link() {
+        let app = TestModuleBuilder::new().build();
+        let use_case: &dyn VerifyEmailCase = app.module().resolve_ref();
+        let (user, token) = register(&app).await;
+
+        let verified = use_case.execute(token.clone()).await.unwrap();
+        let again = use_case.execute(token).await;
+        let stored = app.users.find_by_id(user.id).await.unwrap().unwrap();
+
+        assert!(verified.validated);
+        assert!(stored.validated);
+        assert!(matches!(again, Err(UserError::InvalidVerificationToken)));
+    }
+
+    #[tokio::test]
+    async fn keeps_the_link_valid_if_the_user_is_not_updated() {
+        let app = TestModuleBuilder::new().build();
+        let use_case: &dyn VerifyEmailCase = app.module().resolve_ref();
+        let (user, token) = register(&app).await;
+
+        app.users.fail_updates(true);
+        let failed = use_case.execute(token.clone()).await;
+
+        app.users.fail_updates(false);
+        let retried = use_case.execute(token).await;
+        let stored = app.users.find_by_id(user.id).await.unwrap().unwrap();
+
+        assert!(matches!(failed, Err(UserError::UnexpectedError(_))));
+        assert!(retried.is_ok());
+        assert!(stored.validated);
+    }
+}
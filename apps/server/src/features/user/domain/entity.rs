@@ -1,6 +1,8 @@
 use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
+use super::errors::UserError;
+
 #[derive(Debug, Clone)]
 pub struct User {
     pub id: Uuid,
@@ -10,4 +12,30 @@ pub struct User {
     pub validated: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+impl User {
+    // Guard for use cases that must refuse accounts whose email
+    // address has not been verified yet.
+
+    pub fn ensure_validated(&self) -> Result<(), UserError> {
+        match self.validated {
+            true => Ok(()),
+            false => Err(UserError::NotValidated),
+        }
+    }
+}
+
+// A single-use token sent by email to verify the address of a user.
+// Only the hash of the token is stored.
+
+#[derive(Debug, Clone)]
+pub struct VerificationToken {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub token_hash: String,
+    pub expires_at: DateTime<Utc>,
+    pub used_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
 }
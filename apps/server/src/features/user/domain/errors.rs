@@ -1,9 +1,82 @@
+use std::{error::Error, fmt};
+
+use crate::shared::domain::{BoxError, TransactionError};
+
+// Failures of infrastructure (database, hashing, mailing) keep their
+// cause, so it can be logged when the error reaches the HTTP layer.
+
 #[derive(Debug)]
 pub enum UserError {
     NotFound,
     EmailAlreadyExists,
     UsernameAlreadyExists,
-    UnexpectedError,
     InvalidEmail,
     InvalidId,
+    NotValidated,
+    InvalidVerificationToken,
+    InvalidCursor,
+    // The database could not be reached in time (pool timeout, closed
+    // pool, network errors)
+    DatabaseUnavailable(BoxError),
+    // A constraint other than the username/email uniqueness was violated
+    ConstraintViolation {
+        constraint: Option<String>,
+        source: BoxError,
+    },
+    UnexpectedError(BoxError),
+}
+
+impl UserError {
+    pub fn unexpected(error: impl Into<BoxError>) -> Self {
+        UserError::UnexpectedError(error.into())
+    }
+}
+
+impl fmt::Display for UserError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UserError::NotFound => write!(f, "user not found"),
+            UserError::EmailAlreadyExists => write!(f, "email already exists"),
+            UserError::UsernameAlreadyExists => write!(f, "username already exists"),
+            UserError::InvalidEmail => write!(f, "invalid email"),
+            UserError::InvalidId => write!(f, "invalid user id"),
+            UserError::NotValidated => write!(f, "email not verified"),
+            UserError::InvalidVerificationToken => {
+                write!(f, "invalid verification token")
+            }
+            UserError::InvalidCursor => write!(f, "invalid pagination cursor"),
+            UserError::DatabaseUnavailable(_) => write!(f, "database unavailable"),
+            UserError::ConstraintViolation { constraint, .. } => write!(
+                f,
+                "constraint {} violated",
+                constraint.as_deref().unwrap_or("<unknown>")
+            ),
+            UserError::UnexpectedError(_) => write!(f, "unexpected error"),
+        }
+    }
+}
+
+impl Error for UserError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            UserError::DatabaseUnavailable(source)
+            | UserError::ConstraintViolation { source, .. }
+            | UserError::UnexpectedError(source) => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+// A unit of work that can't begin, commit or roll back is reported like
+// the failures of the repositories
+
+impl From<TransactionError> for UserError {
+    fn from(error: TransactionError) -> Self {
+        match error {
+            TransactionError::Unavailable(source) => {
+                UserError::DatabaseUnavailable(source)
+            }
+            TransactionError::Failed(_) => UserError::unexpected(error),
+        }
+    }
 }
@@ -1,7 +1,11 @@
 mod entity;
 mod errors;
+mod query;
 mod repository;
+mod transaction;
 
 pub use entity::*;
 pub use errors::*;
+pub use query::*;
 pub use repository::*;
+pub use transaction::*;
@@ -0,0 +1,51 @@
+use chrono::{DateTime, SecondsFormat, Utc};
+
+use super::entity::User;
+use crate::shared::domain::{Cursor, Pagination, SortDirection};
+
+// Criteria to list users, every filter is optional and they are
+// combined with AND. Prefix filters are case insensitive.
+
+#[derive(Debug, Clone, Default)]
+pub struct UserFilters {
+    pub username_prefix: Option<String>,
+    pub email_prefix: Option<String>,
+    pub validated: Option<bool>,
+    pub created_from: Option<DateTime<Utc>>,
+    pub created_to: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UserSortField {
+    CreatedAt,
+    UpdatedAt,
+    Username,
+    Email,
+}
+
+impl UserSortField {
+    // Cursor pointing right after `user` when sorting by this field
+
+    pub fn cursor_for(&self, user: &User) -> Cursor {
+        let value = match self {
+            UserSortField::CreatedAt => {
+                user.created_at.to_rfc3339_opts(SecondsFormat::AutoSi, true)
+            }
+            UserSortField::UpdatedAt => {
+                user.updated_at.to_rfc3339_opts(SecondsFormat::AutoSi, true)
+            }
+            UserSortField::Username => user.username.clone(),
+            UserSortField::Email => user.email.clone(),
+        };
+
+        Cursor { value, id: user.id }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct UserQuery {
+    pub filters: UserFilters,
+    pub sort_by: UserSortField,
+    pub direction: SortDirection,
+    pub pagination: Pagination,
+}
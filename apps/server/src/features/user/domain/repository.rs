@@ -1,16 +1,54 @@
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use shaku::Interface;
 use uuid::Uuid;
 
-use super::{entity::User, errors::UserError};
+use super::{
+    entity::{User, VerificationToken},
+    errors::UserError,
+    query::UserQuery,
+};
+use crate::shared::domain::Page;
 
 #[async_trait]
 pub trait UserRepository: Interface {
-    async fn find_all(&self) -> Result<Vec<User>, UserError>;
+    async fn find_all(&self, query: &UserQuery) -> Result<Page<User>, UserError>;
     async fn find_by_id(&self, user_id: Uuid) -> Result<Option<User>, UserError>;
     async fn find_by_email(&self, email: &str) -> Result<Option<User>, UserError>;
     async fn find_by_username(&self, name: &str) -> Result<Option<User>, UserError>;
     async fn create(&self, user: User) -> Result<User, UserError>;
+    // `update` writes every field back and fails with `NotFound` if the
+    // user is gone. `mark_validated` only sets the flag, so it can't undo
+    // a concurrent change of the other fields.
+
     async fn update(&self, user: User) -> Result<User, UserError>;
-    async fn delete(&self, user_id: Uuid) -> Result<(), UserError>;
+    async fn mark_validated(&self, user_id: Uuid) -> Result<User, UserError>;
+
+    // Soft-deleted users are invisible to every other method, `restore`
+    // brings them back and `purge` removes them (deleted or not) for good
+
+    async fn soft_delete(&self, user_id: Uuid) -> Result<bool, UserError>;
+    async fn restore(&self, user_id: Uuid) -> Result<bool, UserError>;
+    async fn purge(&self, user_id: Uuid) -> Result<bool, UserError>;
+    async fn purge_deleted_before(
+        &self,
+        cutoff: DateTime<Utc>,
+    ) -> Result<u64, UserError>;
+}
+
+// `mark_used` returns whether the token was still unused, so the same
+// verification link can't be consumed twice.
+
+#[async_trait]
+pub trait VerificationTokenRepository: Interface {
+    async fn find_by_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<VerificationToken>, UserError>;
+    async fn create(
+        &self,
+        token: VerificationToken,
+    ) -> Result<VerificationToken, UserError>;
+    async fn mark_used(&self, id: Uuid) -> Result<bool, UserError>;
+    async fn delete_by_user(&self, user_id: Uuid) -> Result<(), UserError>;
 }
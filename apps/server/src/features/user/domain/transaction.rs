@@ -0,0 +1,19 @@
+use async_trait::async_trait;
+use shaku::Interface;
+
+use super::repository::{UserRepository, VerificationTokenRepository};
+use crate::shared::domain::{Transaction, TransactionError};
+
+// Unit of work of the user feature (see `shared::domain::Transaction`),
+// what the repositories of a transaction write is only visible to others
+// once it is committed.
+
+#[async_trait]
+pub trait UserUnitOfWork: Interface {
+    async fn begin(&self) -> Result<Box<dyn UserTransaction>, TransactionError>;
+}
+
+pub trait UserTransaction: Transaction {
+    fn users(&self) -> &dyn UserRepository;
+    fn verification_tokens(&self) -> &dyn VerificationTokenRepository;
+}
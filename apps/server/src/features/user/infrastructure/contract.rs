@@ -0,0 +1,152 @@
+use crate::features::user::domain::{
+    User, UserError, UserFilters, UserQuery, UserRepository, UserSortField,
+    UserUnitOfWork,
+};
+use crate::shared::{
+    domain::{Pagination, SortDirection},
+    infrastructure::testing::user,
+};
+
+// Behavior every `UserRepository` must have, run against the in-memory
+// repository (`memory.rs`) and the Postgres one (`repository.rs`). Each
+// test gets an empty repository.
+
+pub async fn usernames_and_emails_are_unique_ignoring_case(
+    repository: &dyn UserRepository,
+) {
+    repository
+        .create(user("alice", "alice@example.com"))
+        .await
+        .unwrap();
+
+    let username = repository.create(user("ALICE", "other@example.com")).await;
+    let email = repository.create(user("bob", "Alice@Example.com")).await;
+
+    assert!(matches!(username, Err(UserError::UsernameAlreadyExists)));
+    assert!(matches!(email, Err(UserError::EmailAlreadyExists)));
+}
+
+pub async fn soft_deleted_users_are_hidden_until_restored(
+    repository: &dyn UserRepository,
+) {
+    let alice = repository
+        .create(user("alice", "alice@example.com"))
+        .await
+        .unwrap();
+
+    assert!(repository.soft_delete(alice.id).await.unwrap());
+    assert!(repository.find_by_id(alice.id).await.unwrap().is_none());
+    assert!(repository
+        .find_by_email("alice@example.com")
+        .await
+        .unwrap()
+        .is_none());
+
+    // The unique indexes are not partial, the username stays reserved
+
+    let taken = repository.create(user("alice", "new@example.com")).await;
+    assert!(matches!(taken, Err(UserError::UsernameAlreadyExists)));
+
+    assert!(repository.restore(alice.id).await.unwrap());
+    assert!(repository.find_by_id(alice.id).await.unwrap().is_some());
+}
+
+pub async fn cursor_pagination_walks_every_page(repository: &dyn UserRepository) {
+    for name in ["erin", "carol", "alice", "dave", "bob"] {
+        let email = format!("{name}@example.com");
+        repository
+            .create(user(&format!("{name}00"), &email))
+            .await
+            .unwrap();
+    }
+
+    let mut query = UserQuery {
+        filters: UserFilters::default(),
+        sort_by: UserSortField::Username,
+        direction: SortDirection::Desc,
+        pagination: Pagination::Cursor {
+            after: None,
+            limit: 2,
+        },
+    };
+    let mut usernames = Vec::new();
+
+    loop {
+        let page = repository.find_all(&query).await.unwrap();
+        assert_eq!(page.total, 5);
+
+        usernames.extend(page.items.into_iter().map(|user| user.username));
+
+        let Some(cursor) = page.next_cursor else {
+            break;
+        };
+        query.pagination = Pagination::Cursor {
+            after: Some(cursor),
+            limit: 2,
+        };
+    }
+
+    assert_eq!(
+        usernames,
+        ["erin00", "dave00", "carol00", "bob00", "alice00"]
+    );
+}
+
+// Updates only reach active users, and validating one leaves the fields
+// written since it was read alone
+
+pub async fn updates_only_reach_active_users(repository: &dyn UserRepository) {
+    let alice = repository
+        .create(user("alice", "alice@example.com"))
+        .await
+        .unwrap();
+
+    let renamed = User {
+        username: "alicia".to_string(),
+        ..alice.clone()
+    };
+    repository.update(renamed).await.unwrap();
+
+    let validated = repository.mark_validated(alice.id).await.unwrap();
+    assert!(validated.validated);
+    assert_eq!(validated.username, "alicia");
+
+    let missing = repository.update(user("ghost", "ghost@example.com")).await;
+    assert!(matches!(missing, Err(UserError::NotFound)));
+
+    repository.soft_delete(alice.id).await.unwrap();
+    let deleted = repository.update(alice.clone()).await;
+    let unvalidated = repository.mark_validated(alice.id).await;
+
+    assert!(matches!(deleted, Err(UserError::NotFound)));
+    assert!(matches!(unvalidated, Err(UserError::NotFound)));
+}
+
+// `repository` is the one the unit of work writes to, outside of its
+// transactions
+
+pub async fn only_committed_writes_are_kept(
+    unit_of_work: &dyn UserUnitOfWork,
+    repository: &dyn UserRepository,
+) {
+    let tx = unit_of_work.begin().await.unwrap();
+    let alice = tx
+        .users()
+        .create(user("alice", "alice@example.com"))
+        .await
+        .unwrap();
+
+    assert!(tx.users().find_by_id(alice.id).await.unwrap().is_some());
+    tx.rollback().await.unwrap();
+    assert!(repository.find_by_id(alice.id).await.unwrap().is_none());
+
+    let tx = unit_of_work.begin().await.unwrap();
+    let bob = tx
+        .users()
+        .create(user("bob", "bob@example.com"))
+        .await
+        .unwrap();
+    tx.commit().await.unwrap();
+
+    assert!(repository.find_by_id(bob.id).await.unwrap().is_some());
+}
@@ -3,59 +3,290 @@ use axum_responses::http::{ControllerResult, HttpResponse};
 use serde_json::json;
 
 use crate::{
-    features::user::{
-        application::interfaces::{
-            CreateUserCase, DeleteUserCase, GetUsersCase, UpdateUserCase,
+    features::{
+        auth::domain::AuthError,
+        role::domain::permissions::{USERS_DELETE, USERS_UPDATE},
+        user::{
+            application::interfaces::{
+                CreateUserCase, DeleteUserCase, GetUserByIdCase, GetUsersCase,
+                PurgeUserCase, ResendVerificationCase, RestoreUserCase,
+                UpdateUserCase, VerifyEmailCase,
+            },
+            domain::UserQuery,
+            infrastructure::{
+                dtos::{
+                    encode_cursor, CreateUserDto, FieldsQuery, GetUsersQuery,
+                    ResendVerificationDto, UpdateUserDto, UserProjection,
+                    VerifyEmailQuery,
+                },
+                openapi::{UserBody, UserListBody},
+            },
+        },
+    },
+    shared::{
+        domain::Pagination,
+        infrastructure::{
+            extractors::{AuthUser, BodyValidator, QueryValidator, UseCase},
+            openapi::MessageResponse,
         },
-        infrastructure::dtos::{CreateUserDto, UpdateUserDto},
     },
-    shared::infrastructure::{extractors::BodyValidator, Inject},
 };
 
-use super::models::{UserModel, UserResponseDTO};
+#[utoipa::path(
+    get,
+    path = "/users",
+    tag = "users",
+    params(GetUsersQuery),
+    security(("bearerAuth" = [])),
+    responses((status = 200, description = "A page of users", body = UserListBody))
+)]
+pub async fn get_users(
+    use_case: UseCase<dyn GetUsersCase>,
+    QueryValidator(params): QueryValidator<GetUsersQuery>,
+) -> ControllerResult {
+    let projection = UserProjection::new(params.fields.as_deref());
+    let query = UserQuery::try_from(params)?;
+    let sort_by = query.sort_by;
+    let pagination = query.pagination.clone();
+
+    let page = use_case.run(|use_case| use_case.execute(query)).await?;
+    let users = projection.many(page.items);
+
+    let next_cursor = page
+        .next_cursor
+        .map(|cursor| encode_cursor(sort_by, &cursor));
 
-pub async fn get_users(use_case: Inject<dyn GetUsersCase>) -> ControllerResult {
-    let data = use_case.execute().await?;
-    let users: Vec<UserResponseDTO> =
-        data.into_iter().map(UserResponseDTO::from).collect();
+    let meta = match pagination {
+        Pagination::Offset {
+            page: number,
+            per_page,
+        } => json!({
+            "total": page.total,
+            "page": number,
+            "perPage": per_page,
+            "totalPages": (page.total + i64::from(per_page) - 1) / i64::from(per_page),
+        }),
+        Pagination::Cursor { limit, .. } => json!({
+            "total": page.total,
+            "limit": limit,
+            "nextCursor": next_cursor,
+        }),
+    };
 
     HttpResponse::build()
         .code(200)
-        .body(json!({ "data": users }))
+        .body(json!({ "data": users, "meta": meta }))
         .wrap()
 }
 
+#[utoipa::path(
+    get,
+    path = "/users/{id}",
+    tag = "users",
+    params(("id" = Uuid, Path, description = "Id of the user"), FieldsQuery),
+    responses((status = 200, description = "The user", body = UserBody))
+)]
+pub async fn get_user_by_id(
+    use_case: UseCase<dyn GetUserByIdCase>,
+    Path(id): Path<String>,
+    QueryValidator(fields): QueryValidator<FieldsQuery>,
+) -> ControllerResult {
+    let user = use_case.run(|use_case| use_case.execute(id)).await?;
+
+    HttpResponse::build()
+        .status(StatusCode::OK)
+        .body(json!({ "data": UserProjection::from(&fields).one(user) }))
+        .wrap()
+}
+
+#[utoipa::path(
+    post,
+    path = "/users/",
+    tag = "users",
+    params(FieldsQuery),
+    request_body = CreateUserDto,
+    responses((
+        status = 201,
+        description = "The user was created, a verification link was sent to its email",
+        body = UserBody
+    ))
+)]
 pub async fn create_user(
-    use_case: Inject<dyn CreateUserCase>,
+    use_case: UseCase<dyn CreateUserCase>,
+    QueryValidator(fields): QueryValidator<FieldsQuery>,
     BodyValidator(user_data): BodyValidator<CreateUserDto>,
 ) -> ControllerResult {
-    let user = use_case.execute(user_data.into()).await?;
+    let user = use_case
+        .run(|use_case| use_case.execute(user_data.into()))
+        .await?;
 
     HttpResponse::build()
         .status(StatusCode::CREATED)
-        .body(json!({ "data": UserModel::from(user) }))
+        .body(json!({ "data": UserProjection::from(&fields).one(user) }))
         .wrap()
 }
 
+// Only the owner of the account or users with the corresponding
+// permission (e.g. admins) can update or delete it
+
+#[utoipa::path(
+    patch,
+    path = "/users/{id}",
+    tag = "users",
+    params(("id" = Uuid, Path, description = "Id of the user"), FieldsQuery),
+    request_body = UpdateUserDto,
+    security(("bearerAuth" = [])),
+    responses((status = 200, description = "The updated user", body = UserBody))
+)]
 pub async fn update_user(
-    use_case: Inject<dyn UpdateUserCase>,
+    use_case: UseCase<dyn UpdateUserCase>,
+    auth: AuthUser,
     Path(id): Path<String>,
+    QueryValidator(fields): QueryValidator<FieldsQuery>,
     BodyValidator(user_data): BodyValidator<UpdateUserDto>,
 ) -> ControllerResult {
-    let user = use_case.execute(id, user_data.into()).await?;
+    if !auth.can_manage(&id, USERS_UPDATE) {
+        return Err(AuthError::Forbidden.into());
+    }
+
+    let user = use_case
+        .run(|use_case| use_case.execute(id, user_data.into()))
+        .await?;
     HttpResponse::build()
         .status(StatusCode::OK)
-        .body(json!({ "data": UserModel::from(user) }))
+        .body(json!({ "data": UserProjection::from(&fields).one(user) }))
         .wrap()
 }
 
+#[utoipa::path(
+    delete,
+    path = "/users/{id}",
+    tag = "users",
+    params(("id" = Uuid, Path, description = "Id of the user")),
+    security(("bearerAuth" = [])),
+    responses((
+        status = 200,
+        description = "The user was soft-deleted",
+        body = MessageResponse
+    ))
+)]
 pub async fn delete_user(
-    use_case: Inject<dyn DeleteUserCase>,
+    use_case: UseCase<dyn DeleteUserCase>,
+    auth: AuthUser,
     Path(id): Path<String>,
 ) -> ControllerResult {
-    use_case.execute(id).await?;
+    if !auth.can_manage(&id, USERS_DELETE) {
+        return Err(AuthError::Forbidden.into());
+    }
+
+    use_case.run(|use_case| use_case.execute(id)).await?;
     HttpResponse::build()
         .status(StatusCode::OK)
         .body(json!({ "message": "User deleted successfully" }))
         .wrap()
 }
+
+// Restoring follows the same rules as deleting. A deleted user can't log
+// in nor refresh its session, so the owner can only restore the account
+// with the access token it still holds (until it expires, see
+// `auth.access_token_ttl`). Afterwards only a user with the
+// `users:delete` permission can restore it.
+
+#[utoipa::path(
+    post,
+    path = "/users/{id}/restore",
+    tag = "users",
+    params(("id" = Uuid, Path, description = "Id of the user")),
+    security(("bearerAuth" = [])),
+    responses((
+        status = 200,
+        description = "The soft-deleted user was restored. The owner can only \
+                       do it before its access token expires, deleted users \
+                       can't log in nor refresh their session",
+        body = MessageResponse
+    ))
+)]
+pub async fn restore_user(
+    use_case: UseCase<dyn RestoreUserCase>,
+    auth: AuthUser,
+    Path(id): Path<String>,
+) -> ControllerResult {
+    if !auth.can_manage(&id, USERS_DELETE) {
+        return Err(AuthError::Forbidden.into());
+    }
+
+    use_case.run(|use_case| use_case.execute(id)).await?;
+    HttpResponse::build()
+        .status(StatusCode::OK)
+        .body(json!({ "message": "User restored successfully" }))
+        .wrap()
+}
+
+#[utoipa::path(
+    delete,
+    path = "/users/{id}/purge",
+    tag = "users",
+    params(("id" = Uuid, Path, description = "Id of the user")),
+    security(("bearerAuth" = [])),
+    responses((
+        status = 200,
+        description = "The user was deleted permanently",
+        body = MessageResponse
+    ))
+)]
+pub async fn purge_user(
+    use_case: UseCase<dyn PurgeUserCase>,
+    Path(id): Path<String>,
+) -> ControllerResult {
+    use_case.run(|use_case| use_case.execute(id)).await?;
+    HttpResponse::build()
+        .status(StatusCode::OK)
+        .body(json!({ "message": "User purged successfully" }))
+        .wrap()
+}
+
+#[utoipa::path(
+    get,
+    path = "/users/verify",
+    tag = "users",
+    params(VerifyEmailQuery),
+    responses((status = 200, description = "The verified user", body = UserBody))
+)]
+pub async fn verify_email(
+    use_case: UseCase<dyn VerifyEmailCase>,
+    QueryValidator(query): QueryValidator<VerifyEmailQuery>,
+) -> ControllerResult {
+    let user = use_case
+        .run(|use_case| use_case.execute(query.token))
+        .await?;
+
+    HttpResponse::build()
+        .status(StatusCode::OK)
+        .body(json!({ "data": UserProjection::new(None).one(user) }))
+        .wrap()
+}
+
+#[utoipa::path(
+    post,
+    path = "/users/verify/resend",
+    tag = "users",
+    request_body = ResendVerificationDto,
+    responses((
+        status = 202,
+        description = "Sent regardless of the account existing, to not disclose it",
+        body = MessageResponse
+    ))
+)]
+pub async fn resend_verification(
+    use_case: UseCase<dyn ResendVerificationCase>,
+    BodyValidator(dto): BodyValidator<ResendVerificationDto>,
+) -> ControllerResult {
+    use_case.run(|use_case| use_case.execute(dto.email)).await?;
+
+    HttpResponse::build()
+        .status(StatusCode::ACCEPTED)
+        .body(json!({
+            "message": "If the account exists and is not verified, a new link was sent"
+        }))
+        .wrap()
+}
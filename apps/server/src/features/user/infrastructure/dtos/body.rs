@@ -11,6 +11,7 @@
 // |----------------------------------------------------------------|
 
 use serde::Deserialize;
+use utoipa::ToSchema;
 use validator::Validate;
 
 use crate::features::user::application::interfaces::{
@@ -18,7 +19,8 @@ use crate::features::user::application::interfaces::{
 };
 
 use super::validators::{
-    password_schema, validate_optional_password_pairs, validate_password_pairs,
+    password_field, password_schema, validate_optional_password_pairs,
+    validate_password_pairs,
 };
 
 // The DTOs are used to validate the incoming request data
@@ -31,17 +33,26 @@ use super::validators::{
 // This is necessary bc the field name in the JSON request body
 // does not match the field name in the struct
 
-#[derive(Deserialize, Validate)]
+// The `#[schema]` attributes mirror the rules in the OpenAPI specification,
+// the `documented_bounds_match_the_validation` tests fail if they drift
+// from the `#[validate]` ones
+
+#[derive(Deserialize, Validate, ToSchema)]
 #[validate(schema(function = "validate_password_pairs"))]
 pub struct CreateUserDto {
     #[validate(length(min = 5, max = 50))]
+    #[schema(min_length = 5, max_length = 50)]
     pub username: String,
     #[validate(email)]
+    #[schema(format = Email)]
     pub email: String,
     #[validate(custom(function = "password_schema"))]
+    #[schema(schema_with = password_field)]
     pub password: String,
     #[validate(custom(function = "password_schema"))]
     #[serde(rename = "confirmPassword")]
+    #[schema(schema_with = password_field)]
+    /// Must be equal to `password`
     pub confirm_password: String,
 }
 
@@ -61,17 +72,22 @@ impl From<CreateUserDto> for CreateUserInput {
     }
 }
 
-#[derive(Deserialize, Validate)]
+#[derive(Deserialize, Validate, ToSchema)]
 #[validate(schema(function = "validate_optional_password_pairs"))]
 pub struct UpdateUserDto {
     #[validate(length(min = 5, max = 50))]
+    #[schema(min_length = 5, max_length = 50)]
     pub username: Option<String>,
     #[validate(email)]
+    #[schema(format = Email)]
     pub email: Option<String>,
     #[validate(custom(function = "password_schema"))]
+    #[schema(schema_with = password_field)]
     pub password: Option<String>,
     #[validate(custom(function = "password_schema"))]
     #[serde(rename = "confirmPassword")]
+    #[schema(schema_with = password_field)]
+    /// Required with `password`, and equal to it
     pub confirm_password: Option<String>,
 }
 
@@ -90,3 +106,10 @@ impl From<UpdateUserDto> for UpdateUserInput {
         }
     }
 }
+
+#[derive(Deserialize, Validate, ToSchema)]
+pub struct ResendVerificationDto {
+    #[validate(email)]
+    #[schema(format = Email)]
+    pub email: String,
+}
@@ -0,0 +1,260 @@
+// This module contains the query string DTOs for the user endpoints.
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::Deserialize;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+use validator::Validate;
+
+use super::{response::fields_schema, validators::validate_pagination_mode};
+use crate::{
+    features::user::domain::{UserError, UserFilters, UserQuery, UserSortField},
+    shared::domain::{Cursor, Pagination, SortDirection},
+};
+
+#[derive(Deserialize, Validate, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct VerifyEmailQuery {
+    /// Token of the link sent by email
+    #[validate(length(min = 1, max = 128))]
+    #[param(min_length = 1, max_length = 128)]
+    pub token: String,
+}
+
+// |----------------------------------------------------------------|
+// |  User Infrastructure Layer (GetUsersQuery)  |     Controller     |
+// |----------------------------------------------------------------|
+// |     User Domain Layer (UserQuery)           |  Use Case | Repo   |
+// |----------------------------------------------------------------|
+
+// `GET /users` accepts two pagination strategies, they can't be mixed:
+
+// - Offset: `?page=2&perPage=20` (default when no cursor is given)
+// - Cursor: `?cursor=<nextCursor>&limit=20`, or `?limit=20` for the
+//   first page. The cursor is opaque and tied to the `sortBy` field.
+
+#[derive(Deserialize, Clone, Copy, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum UserSortKey {
+    CreatedAt,
+    UpdatedAt,
+    Username,
+    Email,
+}
+
+#[derive(Deserialize, Clone, Copy, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    Asc,
+    Desc,
+}
+
+#[derive(Deserialize, Validate, IntoParams)]
+#[serde(rename_all = "camelCase")]
+#[into_params(parameter_in = Query)]
+#[validate(schema(function = "validate_pagination_mode"))]
+pub struct GetUsersQuery {
+    /// Offset pagination, defaults to 1
+    #[validate(range(min = 1))]
+    #[param(minimum = 1)]
+    pub page: Option<u32>,
+    /// Offset pagination, defaults to 20
+    #[validate(range(min = 1, max = 100))]
+    #[param(minimum = 1, maximum = 100)]
+    pub per_page: Option<u32>,
+    /// Cursor pagination, the `nextCursor` of the previous page
+    #[validate(length(min = 1, max = 512))]
+    #[param(min_length = 1, max_length = 512)]
+    pub cursor: Option<String>,
+    /// Cursor pagination, defaults to 20
+    #[validate(range(min = 1, max = 100))]
+    #[param(minimum = 1, maximum = 100)]
+    pub limit: Option<u32>,
+    /// Prefix of the username, case-insensitive
+    #[validate(length(min = 1, max = 50))]
+    #[param(min_length = 1, max_length = 50)]
+    pub username: Option<String>,
+    /// Prefix of the email, case-insensitive
+    #[validate(length(min = 1, max = 255))]
+    #[param(min_length = 1, max_length = 255)]
+    pub email: Option<String>,
+    pub validated: Option<bool>,
+    pub created_from: Option<DateTime<Utc>>,
+    /// Must not be before `createdFrom`
+    pub created_to: Option<DateTime<Utc>>,
+    /// Defaults to `createdAt`
+    pub sort_by: Option<UserSortKey>,
+    /// Defaults to `desc`
+    pub order: Option<SortOrder>,
+    /// Comma separated subset of the user fields to return
+    #[validate(custom(function = "fields_schema"))]
+    #[param(example = "id,username")]
+    pub fields: Option<String>,
+}
+
+pub const DEFAULT_PER_PAGE: u32 = 20;
+
+impl From<UserSortKey> for UserSortField {
+    fn from(key: UserSortKey) -> Self {
+        match key {
+            UserSortKey::CreatedAt => UserSortField::CreatedAt,
+            UserSortKey::UpdatedAt => UserSortField::UpdatedAt,
+            UserSortKey::Username => UserSortField::Username,
+            UserSortKey::Email => UserSortField::Email,
+        }
+    }
+}
+
+fn sort_key_name(field: UserSortField) -> &'static str {
+    match field {
+        UserSortField::CreatedAt => "createdAt",
+        UserSortField::UpdatedAt => "updatedAt",
+        UserSortField::Username => "username",
+        UserSortField::Email => "email",
+    }
+}
+
+// The cursor is sent to the client as `base64url("<sortBy>|<value>|<id>")`
+
+pub fn encode_cursor(sort_by: UserSortField, cursor: &Cursor) -> String {
+    let raw = format!("{}|{}|{}", sort_key_name(sort_by), cursor.value, cursor.id);
+    URL_SAFE_NO_PAD.encode(raw)
+}
+
+fn decode_cursor(
+    sort_by: UserSortField,
+    encoded: &str,
+) -> Result<Cursor, UserError> {
+    let bytes = URL_SAFE_NO_PAD
+        .decode(encoded)
+        .map_err(|_| UserError::InvalidCursor)?;
+    let raw = String::from_utf8(bytes).map_err(|_| UserError::InvalidCursor)?;
+
+    let (field, rest) = raw.split_once('|').ok_or(UserError::InvalidCursor)?;
+    let (value, id) = rest.rsplit_once('|').ok_or(UserError::InvalidCursor)?;
+
+    if field != sort_key_name(sort_by) {
+        return Err(UserError::InvalidCursor);
+    }
+
+    // The value is bound as a parameter and cast to the sort column, a
+    // tampered one has to be rejected here rather than by the database
+
+    let value = match sort_by {
+        UserSortField::CreatedAt | UserSortField::UpdatedAt => {
+            DateTime::parse_from_rfc3339(value)
+                .map_err(|_| UserError::InvalidCursor)?
+                .with_timezone(&Utc)
+                .to_rfc3339_opts(SecondsFormat::AutoSi, true)
+        }
+        UserSortField::Username | UserSortField::Email => {
+            if value.contains('\0') {
+                return Err(UserError::InvalidCursor);
+            }
+            value.to_string()
+        }
+    };
+
+    Ok(Cursor {
+        value,
+        id: Uuid::parse_str(id).map_err(|_| UserError::InvalidCursor)?,
+    })
+}
+
+// | Controller (GetUsersQuery) -> Use Case (UserQuery) |
+
+impl TryFrom<GetUsersQuery> for UserQuery {
+    type Error = UserError;
+
+    fn try_from(dto: GetUsersQuery) -> Result<Self, Self::Error> {
+        let sort_by = dto
+            .sort_by
+            .map(UserSortField::from)
+            .unwrap_or(UserSortField::CreatedAt);
+
+        let direction = match dto.order {
+            Some(SortOrder::Asc) => SortDirection::Asc,
+            Some(SortOrder::Desc) | None => SortDirection::Desc,
+        };
+
+        // Mixed strategies are rejected by `validate_pagination_mode`
+
+        let is_cursor = dto.cursor.is_some() || dto.limit.is_some();
+
+        let pagination = if is_cursor {
+            Pagination::Cursor {
+                after: dto
+                    .cursor
+                    .as_deref()
+                    .map(|cursor| decode_cursor(sort_by, cursor))
+                    .transpose()?,
+                limit: dto.limit.unwrap_or(DEFAULT_PER_PAGE),
+            }
+        } else {
+            Pagination::Offset {
+                page: dto.page.unwrap_or(1),
+                per_page: dto.per_page.unwrap_or(DEFAULT_PER_PAGE),
+            }
+        };
+
+        Ok(UserQuery {
+            filters: UserFilters {
+                username_prefix: dto.username,
+                email_prefix: dto.email,
+                validated: dto.validated,
+                created_from: dto.created_from,
+                created_to: dto.created_to,
+            },
+            sort_by,
+            direction,
+            pagination,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode(raw: &str) -> String {
+        URL_SAFE_NO_PAD.encode(raw)
+    }
+
+    #[test]
+    fn round_trips_the_cursors_it_encodes() {
+        let cursor = Cursor {
+            value: "2024-05-01T10:00:00.123456Z".to_string(),
+            id: Uuid::new_v4(),
+        };
+        let encoded = encode_cursor(UserSortField::CreatedAt, &cursor);
+        let decoded = decode_cursor(UserSortField::CreatedAt, &encoded).unwrap();
+
+        assert_eq!(decoded.value, cursor.value);
+        assert_eq!(decoded.id, cursor.id);
+    }
+
+    #[test]
+    fn rejects_a_cursor_with_a_malformed_timestamp() {
+        let id = Uuid::new_v4();
+
+        for value in ["yesterday", "2024-13-01T10:00:00Z", ""] {
+            let encoded = encode(&format!("createdAt|{value}|{id}"));
+
+            assert!(matches!(
+                decode_cursor(UserSortField::CreatedAt, &encoded),
+                Err(UserError::InvalidCursor)
+            ));
+        }
+    }
+
+    #[test]
+    fn rejects_a_cursor_of_another_sort_field() {
+        let encoded = encode(&format!("username|alice|{}", Uuid::new_v4()));
+
+        assert!(matches!(
+            decode_cursor(UserSortField::CreatedAt, &encoded),
+            Err(UserError::InvalidCursor)
+        ));
+    }
+}
@@ -0,0 +1,135 @@
+// This module contains the response projection for the user endpoints.
+
+// |----------------------------------------------------------------|
+// |                Output entities between layers                  |
+// |----------------------------------------------------------------|
+// | User Infrastructure Layer (UserProjection) |     Controller     |
+// |----------------------------------------------------------------|
+// |      User Application Layer (User)         |      Use Case      |
+// |----------------------------------------------------------------|
+
+// Every handler that returns users goes through `UserProjection`, it
+// is the only serializable representation of a user. The persistence
+// model (`UserModel`) does not implement `Serialize`, so the password
+// hash can't reach a response by mistake.
+
+use std::borrow::Cow;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+use validator::{Validate, ValidationError};
+
+use crate::shared::infrastructure::validation::invalid;
+
+use crate::features::user::domain::User;
+
+// Fields that can be requested with `?fields=id,username`
+
+pub const USER_FIELDS: [&str; 6] = [
+    "id",
+    "username",
+    "email",
+    "validated",
+    "createdAt",
+    "updatedAt",
+];
+
+// Also the schema of a user in the OpenAPI specification, a selection
+// of `?fields` returns a subset of it. Its keys are camelCase like the
+// rest of the API, and `?fields` uses the same names.
+
+#[derive(Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[schema(as = User)]
+pub struct UserResponseDTO {
+    id: Uuid,
+    username: String,
+    email: String,
+    validated: bool,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl From<User> for UserResponseDTO {
+    fn from(user: User) -> Self {
+        UserResponseDTO {
+            id: user.id,
+            username: user.username,
+            email: user.email,
+            validated: user.validated,
+            created_at: user.created_at,
+            updated_at: user.updated_at,
+        }
+    }
+}
+
+#[derive(Deserialize, Validate, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct FieldsQuery {
+    /// Comma separated subset of the user fields to return
+    #[validate(custom(function = "fields_schema"))]
+    #[param(example = "id,username")]
+    pub fields: Option<String>,
+}
+
+pub fn fields_schema(fields: &str) -> Result<(), ValidationError> {
+    let all_known = fields
+        .split(',')
+        .map(str::trim)
+        .all(|field| USER_FIELDS.contains(&field));
+
+    if !all_known {
+        let mut error = invalid("unknown_field");
+        error.add_param(Cow::Borrowed("allowed"), &USER_FIELDS.join(", "));
+        return Err(error);
+    }
+
+    Ok(())
+}
+
+// Shapes users into JSON, keeping only the selected fields when a
+// selection was given. The selection must be validated beforehand.
+
+pub struct UserProjection {
+    fields: Option<Vec<String>>,
+}
+
+impl UserProjection {
+    pub fn new(fields: Option<&str>) -> Self {
+        let fields = fields.map(|fields| {
+            fields
+                .split(',')
+                .map(|field| field.trim().to_string())
+                .collect()
+        });
+
+        UserProjection { fields }
+    }
+
+    pub fn one(&self, user: User) -> Value {
+        let value =
+            serde_json::to_value(UserResponseDTO::from(user)).unwrap_or(Value::Null);
+
+        match (&self.fields, value) {
+            (Some(fields), Value::Object(map)) => Value::Object(
+                map.into_iter()
+                    .filter(|(key, _)| fields.contains(key))
+                    .collect::<Map<String, Value>>(),
+            ),
+            (_, value) => value,
+        }
+    }
+
+    pub fn many(&self, users: Vec<User>) -> Vec<Value> {
+        users.into_iter().map(|user| self.one(user)).collect()
+    }
+}
+
+impl From<&FieldsQuery> for UserProjection {
+    fn from(query: &FieldsQuery) -> Self {
+        UserProjection::new(query.fields.as_deref())
+    }
+}
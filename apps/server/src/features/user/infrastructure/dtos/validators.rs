@@ -1,11 +1,20 @@
 use regex::Regex;
+use utoipa::openapi::{
+    schema::{KnownFormat, SchemaFormat, Type},
+    Object, ObjectBuilder,
+};
 use validator::ValidationError;
 
-use super::body::{CreateUserDto, UpdateUserDto};
+use crate::shared::infrastructure::validation::{invalid, invalid_field};
+
+use super::{
+    body::{CreateUserDto, UpdateUserDto},
+    query::GetUsersQuery,
+};
 
 pub fn validate_password_pairs(dto: &CreateUserDto) -> Result<(), ValidationError> {
     if dto.password != dto.confirm_password {
-        return Err(ValidationError::new("Passwords must match"));
+        return Err(invalid_field("confirmPassword", "passwords_mismatch"));
     }
 
     Ok(())
@@ -16,53 +25,92 @@ pub fn validate_optional_password_pairs(
 ) -> Result<(), ValidationError> {
     match (&dto.password, &dto.confirm_password) {
         (Some(pwd), Some(conf)) if pwd != conf => {
-            Err(ValidationError::new("Passwords must match"))
+            Err(invalid_field("confirmPassword", "passwords_mismatch"))
+        }
+        (Some(_), None) | (None, Some(_)) => {
+            Err(invalid_field("confirmPassword", "password_pair_incomplete"))
         }
-        (Some(_), None) | (None, Some(_)) => Err(ValidationError::new(
-            "Either provide both password fields or neither",
-        )),
         _ => Ok(()),
     }
 }
 
+pub fn validate_pagination_mode(dto: &GetUsersQuery) -> Result<(), ValidationError> {
+    let is_cursor = dto.cursor.is_some() || dto.limit.is_some();
+    let is_offset = dto.page.is_some() || dto.per_page.is_some();
+
+    if is_cursor && is_offset {
+        return Err(invalid("pagination_mode_conflict"));
+    }
+
+    if let (Some(from), Some(to)) = (dto.created_from, dto.created_to) {
+        if from > to {
+            return Err(invalid_field("createdTo", "invalid_date_range"));
+        }
+    }
+
+    Ok(())
+}
+
+// Password rules, shared by the validator and its documentation in the
+// OpenAPI specification (see `password_field`)
+
+const PASSWORD_MIN_LENGTH: usize = 8;
+const PASSWORD_MAX_LENGTH: usize = 100;
+const PASSWORD_SPECIAL_CHARS: &str = r#"[!@#$%^&*()_+\-=\[\]{};':"\\|,.<>\/?]"#;
+
+// The length is counted in characters, like the `minLength`/`maxLength`
+// of the schema
+
 pub fn password_schema(password: &str) -> Result<(), ValidationError> {
-    if password.len() < 8 || password.len() > 100 {
-        return Err(ValidationError::new(
-            "Password must be 8-100 characters long",
-        ));
+    let length = password.chars().count();
+
+    if !(PASSWORD_MIN_LENGTH..=PASSWORD_MAX_LENGTH).contains(&length) {
+        return Err(invalid("password_length"));
     }
 
     let has_uppercase = password.chars().any(|c| c.is_ascii_uppercase());
     let has_lowercase = password.chars().any(|c| c.is_ascii_lowercase());
     let has_digit = password.chars().any(|c| c.is_ascii_digit());
 
-    let special_chars_pattern = r#"[!@#$%^&*()_+\-=\[\]{};':"\\|,.<>\/?]"#;
-    let special_chars_regex = Regex::new(special_chars_pattern).unwrap();
+    let special_chars_regex = Regex::new(PASSWORD_SPECIAL_CHARS).unwrap();
     let has_special = special_chars_regex.is_match(password);
 
     if !has_uppercase {
-        return Err(ValidationError::new(
-            "Password must contain at least one uppercase letter",
-        ));
+        return Err(invalid("password_uppercase"));
     }
 
     if !has_lowercase {
-        return Err(ValidationError::new(
-            "Password must contain at least one lowercase letter",
-        ));
+        return Err(invalid("password_lowercase"));
     }
 
     if !has_digit {
-        return Err(ValidationError::new(
-            "Password must contain at least one digit",
-        ));
+        return Err(invalid("password_digit"));
     }
 
     if !has_special {
-        return Err(ValidationError::new(
-            "Password must contain at least one special character (e.g., !@#$%^&*)",
-        ));
+        return Err(invalid("password_special"));
     }
 
     Ok(())
 }
+
+// The same rules as a JSON schema, the pattern uses lookaheads (one per
+// rule), they are supported by the ECMA regexes of the OpenAPI tools.
+
+pub fn password_field() -> Object {
+    let pattern = format!(
+        "^(?=.*[A-Z])(?=.*[a-z])(?=.*[0-9])(?=.*{PASSWORD_SPECIAL_CHARS}).*$"
+    );
+
+    ObjectBuilder::new()
+        .schema_type(Type::String)
+        .format(Some(SchemaFormat::KnownFormat(KnownFormat::Password)))
+        .min_length(Some(PASSWORD_MIN_LENGTH))
+        .max_length(Some(PASSWORD_MAX_LENGTH))
+        .pattern(Some(pattern))
+        .description(Some(
+            "At least one uppercase letter, one lowercase letter, one digit \
+             and one special character",
+        ))
+        .build()
+}
@@ -1,5 +1,7 @@
 // This file implements the conversion from the `UserError` enum
-// to the `HttpResponse` type.
+// to the `HttpResponse` type, an `application/problem+json` document
+// (see `shared::infrastructure::problem`). The messages of each code
+// live in the catalogs, `locales/<lang>.toml`.
 
 // This is necessary bc the `UserError` enum is used in the internal
 // application and domain layers
@@ -14,55 +16,89 @@
 
 use axum::http::StatusCode;
 use axum_responses::http::HttpResponse;
-use serde_json::json;
 
 use crate::features::user::domain::UserError;
+use crate::shared::{domain::error_chain, infrastructure::problem::Problem};
 
 // Each variant of the `UserError` enum corresponds to a specific error
 // that can occur in the user management process.
 
+// The problem of each variant is also used to document the responses of
+// the routes, see `openapi.rs`.
+
+impl From<&UserError> for Problem {
+    fn from(value: &UserError) -> Self {
+        match value {
+            UserError::UsernameAlreadyExists => {
+                Problem::new(StatusCode::CONFLICT, "user.username_taken")
+                    .field("username")
+            }
+
+            UserError::EmailAlreadyExists => {
+                Problem::new(StatusCode::CONFLICT, "user.email_taken").field("email")
+            }
+
+            UserError::NotFound => {
+                Problem::new(StatusCode::NOT_FOUND, "user.not_found")
+            }
+
+            UserError::DatabaseUnavailable(_) => Problem::new(
+                StatusCode::SERVICE_UNAVAILABLE,
+                "server.database_unavailable",
+            ),
+
+            UserError::ConstraintViolation { .. } => {
+                Problem::new(StatusCode::CONFLICT, "user.constraint_violation")
+            }
+
+            UserError::UnexpectedError(_) => Problem::new(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "server.unexpected_error",
+            ),
+
+            UserError::InvalidEmail => {
+                Problem::new(StatusCode::BAD_REQUEST, "user.invalid_email")
+                    .field("email")
+            }
+
+            UserError::InvalidId => {
+                Problem::new(StatusCode::BAD_REQUEST, "user.invalid_id").field("id")
+            }
+
+            UserError::NotValidated => {
+                Problem::new(StatusCode::FORBIDDEN, "user.email_not_verified")
+                    .field("email")
+            }
+
+            UserError::InvalidVerificationToken => Problem::new(
+                StatusCode::BAD_REQUEST,
+                "user.invalid_verification_token",
+            )
+            .field("token"),
+
+            UserError::InvalidCursor => {
+                Problem::new(StatusCode::BAD_REQUEST, "user.invalid_cursor")
+                    .field("cursor")
+            }
+        }
+    }
+}
+
+// Infrastructure failures are logged with their whole cause chain before
+// it is dropped, the client only gets the generic problem. The event is
+// emitted inside the request span, so it carries its `request_id`.
+
 impl From<UserError> for HttpResponse {
     fn from(value: UserError) -> Self {
-        match value {
-            UserError::UsernameAlreadyExists => HttpResponse::build()
-                .status(StatusCode::CONFLICT)
-                .body(json!({
-                    "field": "username",
-                    "message": "Username already exists",
-                })),
-
-            UserError::EmailAlreadyExists => HttpResponse::build()
-                .status(StatusCode::CONFLICT)
-                .body(json!({
-                    "field": "email",
-                    "message": "Email already exists",
-                })),
-
-            UserError::NotFound => HttpResponse::build()
-                .status(StatusCode::NOT_FOUND)
-                .body(json!({
-                    "message": "User not found",
-                })),
-
-            UserError::UnexpectedError => HttpResponse::build()
-                .status(StatusCode::INTERNAL_SERVER_ERROR)
-                .body(json!({
-                    "message": "Unexpected error",
-                })),
-
-            UserError::InvalidEmail => HttpResponse::build()
-                .status(StatusCode::BAD_REQUEST)
-                .body(json!({
-                    "field": "email",
-                    "message": "The provided email is not valid to register",
-                })),
-
-            UserError::InvalidId => HttpResponse::build()
-                .status(StatusCode::BAD_REQUEST)
-                .body(json!({
-                    "field": "id",
-                    "message": "The provided id is not valid",
-                })),
+        if matches!(
+            value,
+            UserError::DatabaseUnavailable(_)
+                | UserError::ConstraintViolation { .. }
+                | UserError::UnexpectedError(_)
+        ) {
+            tracing::error!(error = %error_chain(&value), "user operation failed");
         }
+
+        Problem::from(&value).into()
     }
 }
@@ -0,0 +1,49 @@
+// Background jobs of the user feature, spawned once at startup.
+
+use shaku::HasComponent;
+use std::time::Duration;
+
+use crate::features::user::application::interfaces::PurgeDeletedUsersCase;
+use crate::shared::{
+    application::{shutdown_hook, ShutdownHooks},
+    domain::error_chain,
+    infrastructure::{metrics::observe, AppState},
+    settings::SettingsProvider,
+};
+
+// Hard-deletes the users soft-deleted longer than the retention period,
+// every `users.purge_interval` seconds. Failures are logged and retried
+// on the next tick. The job is stopped when the server shuts down.
+
+pub fn spawn_retention_job(state: AppState) {
+    let settings: &dyn SettingsProvider = state.module.resolve_ref();
+    let period =
+        Duration::from_secs(settings.get().users.purge_interval.unsigned_abs());
+
+    let hooks: &dyn ShutdownHooks = state.module.resolve_ref();
+    let module = state.module.clone();
+
+    let job = tokio::spawn(async move {
+        let mut interval = tokio::time::interval(period);
+
+        loop {
+            interval.tick().await;
+
+            let use_case: &dyn PurgeDeletedUsersCase = module.resolve_ref();
+
+            match observe(use_case, |use_case| use_case.execute()).await {
+                Ok(0) => {}
+                Ok(purged) => tracing::info!("Purged {purged} deleted users"),
+                Err(error) => tracing::error!(
+                    error = %error_chain(&error),
+                    "failed to purge the deleted users"
+                ),
+            }
+        }
+    });
+
+    hooks.register(
+        "user retention job",
+        shutdown_hook(move || async move { job.abort() }),
+    );
+}
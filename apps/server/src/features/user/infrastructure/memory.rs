@@ -0,0 +1,515 @@
+use std::{
+    cmp::Ordering,
+    sync::{
+        atomic::{AtomicBool, Ordering as AtomicOrdering},
+        Arc, Mutex, MutexGuard,
+    },
+};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::shared::{
+    domain::{Page, Pagination, SortDirection, Transaction, TransactionError},
+    infrastructure::testing::RollbackLog,
+};
+
+use crate::features::user::domain::{
+    User, UserError, UserFilters, UserQuery, UserRepository, UserSortField,
+    UserTransaction, UserUnitOfWork, VerificationToken, VerificationTokenRepository,
+};
+
+// Repositories that keep the users and tokens in memory, used by the tests
+// to run the use cases and controllers without a database. They mirror
+// the behavior of the Postgres ones (see `repository.rs`):
+
+// |------------------------------------------------------------------|
+// |      Postgres (migrations)       |           In memory           |
+// |------------------------------------------------------------------|
+// |  users_email_key (citext)        |  EmailAlreadyExists           |
+// |  users_username_lower_key        |  UsernameAlreadyExists        |
+// |  deleted_at IS NULL              |  soft-deleted users hidden    |
+// |  update_updated_at_column()      |  `updated_at` set on update   |
+// |------------------------------------------------------------------|
+
+// Clones share the same storage, so a test can keep a handle to the
+// repository registered in the module and inspect it, or make its updates
+// fail to check what a use case leaves behind.
+
+#[derive(Clone, Default)]
+pub struct InMemoryUserRepository {
+    users: Arc<Mutex<Vec<User>>>,
+    failing_updates: Arc<AtomicBool>,
+}
+
+impl InMemoryUserRepository {
+    pub fn fail_updates(&self, fail: bool) {
+        self.failing_updates.store(fail, AtomicOrdering::SeqCst);
+    }
+
+    fn users(&self) -> MutexGuard<'_, Vec<User>> {
+        self.users.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub fn restore_on_rollback(&self, log: &mut RollbackLog) {
+        let (repository, users) = (self.clone(), self.users().clone());
+        log.on_rollback(move || *repository.users() = users);
+    }
+
+    fn find_active(&self, predicate: impl Fn(&User) -> bool) -> Option<User> {
+        self.users()
+            .iter()
+            .find(|user| user.deleted_at.is_none() && predicate(user))
+            .cloned()
+    }
+}
+
+#[async_trait]
+impl UserRepository for InMemoryUserRepository {
+    async fn find_all(&self, query: &UserQuery) -> Result<Page<User>, UserError> {
+        let mut users: Vec<User> = self
+            .users()
+            .iter()
+            .filter(|user| matches_filters(user, &query.filters))
+            .cloned()
+            .collect();
+
+        let total = users.len() as i64;
+        let direction = |ordering: Ordering| match query.direction {
+            SortDirection::Asc => ordering,
+            SortDirection::Desc => ordering.reverse(),
+        };
+
+        users.sort_by(|a, b| direction(compare(a, b, query.sort_by)));
+
+        match &query.pagination {
+            Pagination::Offset { page, per_page } => {
+                let offset = page.saturating_sub(1) as usize * *per_page as usize;
+                let items = users
+                    .into_iter()
+                    .skip(offset)
+                    .take(*per_page as usize)
+                    .collect();
+
+                Ok(Page {
+                    items,
+                    total,
+                    next_cursor: None,
+                })
+            }
+            Pagination::Cursor { after, limit } => {
+                let mut items: Vec<User> = users
+                    .into_iter()
+                    .filter(|user| match after {
+                        Some(cursor) => {
+                            let position = compare_cursor(
+                                user,
+                                query.sort_by,
+                                &cursor.value,
+                                cursor.id,
+                            );
+                            direction(position) == Ordering::Greater
+                        }
+                        None => true,
+                    })
+                    .take(*limit as usize + 1)
+                    .collect();
+
+                let mut next_cursor = None;
+
+                if items.len() > *limit as usize {
+                    items.truncate(*limit as usize);
+                    next_cursor =
+                        items.last().map(|user| query.sort_by.cursor_for(user));
+                }
+
+                Ok(Page {
+                    items,
+                    total,
+                    next_cursor,
+                })
+            }
+        }
+    }
+
+    async fn find_by_id(&self, user_id: Uuid) -> Result<Option<User>, UserError> {
+        Ok(self.find_active(|user| user.id == user_id))
+    }
+
+    async fn find_by_email(&self, email: &str) -> Result<Option<User>, UserError> {
+        Ok(self.find_active(|user| fold_case(&user.email) == fold_case(email)))
+    }
+
+    async fn find_by_username(&self, name: &str) -> Result<Option<User>, UserError> {
+        Ok(self.find_active(|user| fold_case(&user.username) == fold_case(name)))
+    }
+
+    async fn create(&self, user: User) -> Result<User, UserError> {
+        let mut users = self.users();
+
+        if users.iter().any(|other| other.id == user.id) {
+            return Err(UserError::ConstraintViolation {
+                constraint: Some("users_pkey".to_string()),
+                source: format!("duplicate user id {}", user.id).into(),
+            });
+        }
+
+        check_unique(&users, &user)?;
+        users.push(user.clone());
+
+        Ok(user)
+    }
+
+    async fn update(&self, user: User) -> Result<User, UserError> {
+        if self.failing_updates.load(AtomicOrdering::SeqCst) {
+            return Err(UserError::unexpected("update failed"));
+        }
+
+        let mut users = self.users();
+
+        let Some(index) = users
+            .iter()
+            .position(|stored| stored.id == user.id && stored.deleted_at.is_none())
+        else {
+            return Err(UserError::NotFound);
+        };
+
+        check_unique(&users, &user)?;
+
+        users[index] = User {
+            updated_at: Utc::now(),
+            deleted_at: None,
+            ..user.clone()
+        };
+
+        Ok(user)
+    }
+
+    async fn mark_validated(&self, user_id: Uuid) -> Result<User, UserError> {
+        if self.failing_updates.load(AtomicOrdering::SeqCst) {
+            return Err(UserError::unexpected("update failed"));
+        }
+
+        let mut users = self.users();
+
+        let Some(user) = users
+            .iter_mut()
+            .find(|user| user.id == user_id && user.deleted_at.is_none())
+        else {
+            return Err(UserError::NotFound);
+        };
+
+        user.validated = true;
+        user.updated_at = Utc::now();
+
+        Ok(user.clone())
+    }
+
+    async fn soft_delete(&self, user_id: Uuid) -> Result<bool, UserError> {
+        let mut users = self.users();
+
+        match users
+            .iter_mut()
+            .find(|user| user.id == user_id && user.deleted_at.is_none())
+        {
+            Some(user) => {
+                user.deleted_at = Some(Utc::now());
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    async fn restore(&self, user_id: Uuid) -> Result<bool, UserError> {
+        let mut users = self.users();
+
+        match users
+            .iter_mut()
+            .find(|user| user.id == user_id && user.deleted_at.is_some())
+        {
+            Some(user) => {
+                user.deleted_at = None;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    async fn purge(&self, user_id: Uuid) -> Result<bool, UserError> {
+        let mut users = self.users();
+        let before = users.len();
+
+        users.retain(|user| user.id != user_id);
+
+        Ok(users.len() < before)
+    }
+
+    async fn purge_deleted_before(
+        &self,
+        cutoff: DateTime<Utc>,
+    ) -> Result<u64, UserError> {
+        let mut users = self.users();
+        let before = users.len();
+
+        users.retain(|user| user.deleted_at.is_none_or(|at| at >= cutoff));
+
+        Ok((before - users.len()) as u64)
+    }
+}
+
+// Case-insensitive values are compared like `lower()` (and so citext)
+// does on a UTF-8 database: each character is lowercased on its own,
+// non-ASCII letters included (`É` matches `é`).
+
+fn fold_case(value: &str) -> String {
+    value.chars().flat_map(char::to_lowercase).collect()
+}
+
+// Same order as the database checks the constraints, soft-deleted users
+// keep their username and email reserved (the indexes are not partial).
+
+fn check_unique(users: &[User], user: &User) -> Result<(), UserError> {
+    let others = || users.iter().filter(|other| other.id != user.id);
+
+    if others().any(|other| fold_case(&other.email) == fold_case(&user.email)) {
+        return Err(UserError::EmailAlreadyExists);
+    }
+
+    if others().any(|other| fold_case(&other.username) == fold_case(&user.username))
+    {
+        return Err(UserError::UsernameAlreadyExists);
+    }
+
+    Ok(())
+}
+
+fn matches_filters(user: &User, filters: &UserFilters) -> bool {
+    let has_prefix = |value: &str, prefix: &Option<String>| {
+        prefix
+            .as_ref()
+            .is_none_or(|prefix| fold_case(value).starts_with(&fold_case(prefix)))
+    };
+
+    user.deleted_at.is_none()
+        && has_prefix(&user.username, &filters.username_prefix)
+        && has_prefix(&user.email, &filters.email_prefix)
+        && filters
+            .validated
+            .is_none_or(|validated| user.validated == validated)
+        && filters
+            .created_from
+            .is_none_or(|from| user.created_at >= from)
+        && filters.created_to.is_none_or(|to| user.created_at <= to)
+}
+
+// Ascending order of the (column, id) pairs, like the `ORDER BY` of the
+// Postgres repository. Emails are compared ignoring case (citext).
+
+fn compare(a: &User, b: &User, field: UserSortField) -> Ordering {
+    let by_field = match field {
+        UserSortField::CreatedAt => a.created_at.cmp(&b.created_at),
+        UserSortField::UpdatedAt => a.updated_at.cmp(&b.updated_at),
+        UserSortField::Username => a.username.cmp(&b.username),
+        UserSortField::Email => fold_case(&a.email).cmp(&fold_case(&b.email)),
+    };
+
+    by_field.then(a.id.cmp(&b.id))
+}
+
+// Position of `user` relative to the cursor, a cursor with an unparsable
+// timestamp matches nothing (Postgres would reject the cast).
+
+fn compare_cursor(
+    user: &User,
+    field: UserSortField,
+    value: &str,
+    id: Uuid,
+) -> Ordering {
+    let timestamp = |at: &DateTime<Utc>| {
+        DateTime::parse_from_rfc3339(value)
+            .map_or(Ordering::Less, |cursor| at.cmp(&cursor.with_timezone(&Utc)))
+    };
+
+    let by_field = match field {
+        UserSortField::CreatedAt => timestamp(&user.created_at),
+        UserSortField::UpdatedAt => timestamp(&user.updated_at),
+        UserSortField::Username => user.username.as_str().cmp(value),
+        UserSortField::Email => fold_case(&user.email).cmp(&fold_case(value)),
+    };
+
+    by_field.then(user.id.cmp(&id))
+}
+
+// Verification tokens, `mark_used` only succeeds once per token. Unlike
+// the database, deleting a user doesn't cascade to its tokens.
+
+#[derive(Clone, Default)]
+pub struct InMemoryVerificationTokenRepository {
+    tokens: Arc<Mutex<Vec<VerificationToken>>>,
+}
+
+impl InMemoryVerificationTokenRepository {
+    fn tokens(&self) -> MutexGuard<'_, Vec<VerificationToken>> {
+        self.tokens.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn restore_on_rollback(&self, log: &mut RollbackLog) {
+        let (repository, tokens) = (self.clone(), self.tokens().clone());
+        log.on_rollback(move || *repository.tokens() = tokens);
+    }
+}
+
+#[async_trait]
+impl VerificationTokenRepository for InMemoryVerificationTokenRepository {
+    async fn find_by_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<VerificationToken>, UserError> {
+        Ok(self
+            .tokens()
+            .iter()
+            .find(|token| token.token_hash == token_hash)
+            .cloned())
+    }
+
+    async fn create(
+        &self,
+        token: VerificationToken,
+    ) -> Result<VerificationToken, UserError> {
+        self.tokens().push(token.clone());
+        Ok(token)
+    }
+
+    async fn mark_used(&self, id: Uuid) -> Result<bool, UserError> {
+        let mut tokens = self.tokens();
+
+        match tokens
+            .iter_mut()
+            .find(|token| token.id == id && token.used_at.is_none())
+        {
+            Some(token) => {
+                token.used_at = Some(Utc::now());
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    async fn delete_by_user(&self, user_id: Uuid) -> Result<(), UserError> {
+        self.tokens().retain(|token| token.user_id != user_id);
+        Ok(())
+    }
+}
+
+// `UserUnitOfWork` over the repositories above, a transaction hands out
+// the same repositories and restores them if it is rolled back (see
+// `RollbackLog`).
+
+#[derive(Clone, Default)]
+pub struct InMemoryUserUnitOfWork {
+    pub users: InMemoryUserRepository,
+    pub verification_tokens: InMemoryVerificationTokenRepository,
+}
+
+#[async_trait]
+impl UserUnitOfWork for InMemoryUserUnitOfWork {
+    async fn begin(&self) -> Result<Box<dyn UserTransaction>, TransactionError> {
+        let mut log = RollbackLog::default();
+        self.users.restore_on_rollback(&mut log);
+        self.verification_tokens.restore_on_rollback(&mut log);
+
+        Ok(Box::new(InMemoryUserTransaction {
+            repositories: self.clone(),
+            log,
+        }))
+    }
+}
+
+struct InMemoryUserTransaction {
+    repositories: InMemoryUserUnitOfWork,
+    log: RollbackLog,
+}
+
+#[async_trait]
+impl Transaction for InMemoryUserTransaction {
+    async fn commit(self: Box<Self>) -> Result<(), TransactionError> {
+        self.log.commit();
+        Ok(())
+    }
+
+    async fn rollback(self: Box<Self>) -> Result<(), TransactionError> {
+        Ok(())
+    }
+}
+
+impl UserTransaction for InMemoryUserTransaction {
+    fn users(&self) -> &dyn UserRepository {
+        &self.repositories.users
+    }
+
+    fn verification_tokens(&self) -> &dyn VerificationTokenRepository {
+        &self.repositories.verification_tokens
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::user::infrastructure::contract;
+    use crate::shared::infrastructure::testing::user;
+
+    #[tokio::test]
+    async fn usernames_and_emails_are_unique_ignoring_case() {
+        let repository = InMemoryUserRepository::default();
+        contract::usernames_and_emails_are_unique_ignoring_case(&repository).await;
+    }
+
+    #[tokio::test]
+    async fn soft_deleted_users_are_hidden_until_restored() {
+        let repository = InMemoryUserRepository::default();
+        contract::soft_deleted_users_are_hidden_until_restored(&repository).await;
+    }
+
+    #[tokio::test]
+    async fn cursor_pagination_walks_every_page() {
+        let repository = InMemoryUserRepository::default();
+        contract::cursor_pagination_walks_every_page(&repository).await;
+    }
+
+    #[tokio::test]
+    async fn updates_only_reach_active_users() {
+        let repository = InMemoryUserRepository::default();
+        contract::updates_only_reach_active_users(&repository).await;
+    }
+
+    #[tokio::test]
+    async fn only_committed_writes_are_kept() {
+        let unit_of_work = InMemoryUserUnitOfWork::default();
+        contract::only_committed_writes_are_kept(&unit_of_work, &unit_of_work.users)
+            .await;
+    }
+
+    // Not part of the contract, the Postgres result depends on the locale
+    // of the database (`lower()` only folds ASCII with `LC_CTYPE=C`)
+
+    #[tokio::test]
+    async fn non_ascii_letters_are_compared_ignoring_case() {
+        let repository = InMemoryUserRepository::default();
+        repository
+            .create(user("elodie", "élodie@example.com"))
+            .await
+            .unwrap();
+
+        let found = repository
+            .find_by_email("ÉLODIE@example.com")
+            .await
+            .unwrap();
+        let duplicated = repository
+            .create(user("elodie2", "Élodie@example.com"))
+            .await;
+
+        assert!(found.is_some());
+        assert!(matches!(duplicated, Err(UserError::EmailAlreadyExists)));
+    }
+}
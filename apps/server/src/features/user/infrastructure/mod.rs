@@ -1,15 +1,33 @@
+#[cfg(test)]
+mod contract;
 mod controllers;
 mod errors;
+mod jobs;
+#[cfg(test)]
+mod memory;
 mod models;
+mod openapi;
 mod repository;
 mod routes;
+mod transaction;
 
 mod dtos {
     mod body;
+    mod query;
+    mod response;
     mod validators;
 
     pub use body::*;
+    pub use query::*;
+    pub use response::*;
+    pub use validators::{password_field, password_schema};
 }
 
+pub use dtos::{password_field, password_schema};
+pub use jobs::spawn_retention_job;
+#[cfg(test)]
+pub use memory::*;
+pub use openapi::UserApi;
 pub use repository::*;
 pub use routes::router as user_router;
+pub use transaction::*;
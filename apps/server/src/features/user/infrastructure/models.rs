@@ -3,7 +3,7 @@
 // |----------------------------------------------------------------|
 // |                Return entities between layers                  |
 // |----------------------------------------------------------------|
-// |  User Infrastructure Layer (UserModel)   | Controller|RepoImpl |
+// |  User Infrastructure Layer (UserModel)   |      RepoImpl       |
 // |------------------------------------------|---------------------|
 // |       User Application Layer (User)      |       Use Case      |
 // |------------------------------------------|---------------------|
@@ -11,21 +11,18 @@
 // |----------------------------------------------------------------|
 
 use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize};
-
 use sqlx::prelude::FromRow;
 use uuid::Uuid;
 
-use crate::features::user::domain::User;
+use crate::features::user::domain::{User, VerificationToken};
 
 // The `UserModel` struct represents the user model in the database.
 // Implements the `FromRow` trait from the `sqlx` crate.
-// Implements the `Serialize` and `Deserialize` traits from the `serde` crate.
 
-// Serialize: UserModel -> JSON
-// Deserialize: JSON | OTHERS -> UserModel
+// It is intentionally not serializable: responses are built from the
+// `User` entity through `UserProjection` (see dtos/response.rs).
 
-#[derive(Serialize, Deserialize, FromRow, Debug, Clone)]
+#[derive(FromRow, Debug, Clone)]
 pub struct UserModel {
     pub id: Uuid,
     pub username: String,
@@ -34,6 +31,7 @@ pub struct UserModel {
     pub validated: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>,
 }
 
 // This is the conversion from the `UserModel` struct to the `User` struct.
@@ -53,49 +51,30 @@ impl From<UserModel> for User {
             validated: user_model.validated,
             created_at: user_model.created_at,
             updated_at: user_model.updated_at,
+            deleted_at: user_model.deleted_at,
         }
     }
 }
 
-// This is the conversion from the `User` struct to the `UserModel` struct.
-// It's the opposite of the previous conversion.
-
-// The use case impl returns a User entity, but the controller
-// needs to return a UserModel struct to the client.
-
-impl From<User> for UserModel {
-    fn from(user: User) -> Self {
-        UserModel {
-            id: user.id,
-            username: user.username,
-            email: user.email,
-            password: user.password,
-            validated: user.validated,
-            created_at: user.created_at,
-            updated_at: user.updated_at,
-        }
-    }
-}
-
-#[derive(Serialize)]
-pub struct UserResponseDTO {
+#[derive(FromRow, Debug, Clone)]
+pub struct VerificationTokenModel {
     pub id: Uuid,
-    pub username: String,
-    pub email: String,
-    pub validated: bool,
+    pub user_id: Uuid,
+    pub token_hash: String,
+    pub expires_at: DateTime<Utc>,
+    pub used_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
-    pub updated_at: DateTime<Utc>,
 }
 
-impl From<User> for UserResponseDTO {
-    fn from(user_model: User) -> Self {
-        UserResponseDTO {
-            id: user_model.id,
-            username: user_model.username,
-            email: user_model.email,
-            validated: user_model.validated,
-            created_at: user_model.created_at,
-            updated_at: user_model.updated_at,
+impl From<VerificationTokenModel> for VerificationToken {
+    fn from(model: VerificationTokenModel) -> Self {
+        VerificationToken {
+            id: model.id,
+            user_id: model.user_id,
+            token_hash: model.token_hash,
+            expires_at: model.expires_at,
+            used_at: model.used_at,
+            created_at: model.created_at,
         }
     }
 }
@@ -0,0 +1,249 @@
+// OpenAPI documentation of the user routes, merged into the specification
+// by `shared::infrastructure::openapi`.
+
+use serde::Serialize;
+use utoipa::{
+    openapi::{path::HttpMethod, OpenApi as Spec},
+    Modify, OpenApi, ToSchema,
+};
+
+use super::controllers::*;
+use super::dtos::{
+    CreateUserDto, ResendVerificationDto, SortOrder, UpdateUserDto, UserResponseDTO,
+    UserSortKey,
+};
+use crate::features::{
+    auth::infrastructure::{authentication_problems, permission_problems},
+    user::domain::UserError,
+};
+use crate::shared::infrastructure::{
+    openapi::{body_problems, document_problems, query_problems},
+    problem::Problem,
+};
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        get_users,
+        get_user_by_id,
+        create_user,
+        update_user,
+        delete_user,
+        restore_user,
+        purge_user,
+        verify_email,
+        resend_verification,
+    ),
+    components(schemas(
+        CreateUserDto,
+        UpdateUserDto,
+        ResendVerificationDto,
+        UserResponseDTO,
+        UserSortKey,
+        SortOrder,
+    )),
+    tags((name = "users", description = "Accounts and their email verification")),
+    modifiers(&UserProblems)
+)]
+pub struct UserApi;
+
+// Envelopes of the responses, the controllers build them with `json!`
+
+#[allow(dead_code)]
+#[derive(ToSchema)]
+pub struct UserBody {
+    data: UserResponseDTO,
+}
+
+#[allow(dead_code)]
+#[derive(ToSchema)]
+pub struct UserListBody {
+    data: Vec<UserResponseDTO>,
+    meta: PageMeta,
+}
+
+// The meta of the page depends on the pagination strategy of the query
+
+#[allow(dead_code)]
+#[derive(Serialize, ToSchema)]
+#[serde(untagged)]
+enum PageMeta {
+    Offset(OffsetPage),
+    Cursor(CursorPage),
+}
+
+#[allow(dead_code)]
+#[derive(Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+struct OffsetPage {
+    total: i64,
+    page: u32,
+    per_page: u32,
+    total_pages: i64,
+}
+
+#[allow(dead_code)]
+#[derive(Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+struct CursorPage {
+    total: i64,
+    limit: u32,
+    /// Absent on the last page
+    next_cursor: Option<String>,
+}
+
+struct UserProblems;
+
+impl Modify for UserProblems {
+    fn modify(&self, spec: &mut Spec) {
+        use HttpMethod::{Delete, Get, Patch, Post};
+        use UserError::*;
+
+        let conflicts = || {
+            [
+                UsernameAlreadyExists,
+                EmailAlreadyExists,
+                ConstraintViolation {
+                    constraint: None,
+                    source: "".into(),
+                },
+            ]
+        };
+
+        document_problems(
+            spec,
+            "/users",
+            Get,
+            [
+                authentication_problems(),
+                permission_problems(),
+                query_problems(),
+                problems([InvalidCursor]),
+            ]
+            .concat(),
+        );
+
+        document_problems(
+            spec,
+            "/users/",
+            Post,
+            [
+                body_problems(),
+                query_problems(),
+                problems([InvalidEmail].into_iter().chain(conflicts())),
+            ]
+            .concat(),
+        );
+
+        document_problems(
+            spec,
+            "/users/{id}",
+            Get,
+            [query_problems(), problems([InvalidId, NotFound])].concat(),
+        );
+
+        document_problems(
+            spec,
+            "/users/{id}",
+            Patch,
+            [
+                authentication_problems(),
+                permission_problems(),
+                body_problems(),
+                query_problems(),
+                problems([InvalidId, NotFound].into_iter().chain(conflicts())),
+            ]
+            .concat(),
+        );
+
+        for (path, method) in [
+            ("/users/{id}", Delete),
+            ("/users/{id}/restore", Post),
+            ("/users/{id}/purge", Delete),
+        ] {
+            document_problems(
+                spec,
+                path,
+                method,
+                [
+                    authentication_problems(),
+                    permission_problems(),
+                    problems([InvalidId, NotFound]),
+                ]
+                .concat(),
+            );
+        }
+
+        document_problems(
+            spec,
+            "/users/verify",
+            Get,
+            [query_problems(), problems([InvalidVerificationToken])].concat(),
+        );
+
+        document_problems(
+            spec,
+            "/users/verify/resend",
+            Post,
+            [body_problems(), problems([])].concat(),
+        );
+    }
+}
+
+// Every user route reaches the database, so any of them can also fail
+// because it is unavailable or with an unexpected error
+
+fn problems(errors: impl IntoIterator<Item = UserError>) -> Vec<Problem> {
+    errors
+        .into_iter()
+        .chain([
+            UserError::DatabaseUnavailable("".into()),
+            UserError::unexpected(""),
+        ])
+        .map(|error| Problem::from(&error))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::features::user::infrastructure::dtos::{
+        GetUsersQuery, VerifyEmailQuery,
+    };
+    use crate::shared::infrastructure::testing::{
+        assert_documented_bounds, query_parameters, schema_properties,
+    };
+
+    #[test]
+    fn documented_bounds_match_the_validation() {
+        let body = json!({
+            "username": "alice",
+            "email": "alice@example.com",
+            "password": "Aa1!",
+            "confirmPassword": "Aa1!",
+        });
+
+        assert_documented_bounds::<CreateUserDto>(
+            &schema_properties("CreateUserDto"),
+            body.clone(),
+        );
+        assert_documented_bounds::<UpdateUserDto>(
+            &schema_properties("UpdateUserDto"),
+            body,
+        );
+        assert_documented_bounds::<GetUsersQuery>(
+            &query_parameters("/users"),
+            json!({}),
+        );
+        assert_documented_bounds::<ResendVerificationDto>(
+            &schema_properties("ResendVerificationDto"),
+            json!({ "email": "alice@example.com" }),
+        );
+        assert_documented_bounds::<VerifyEmailQuery>(
+            &query_parameters("/users/verify"),
+            json!({ "token": "" }),
+        );
+    }
+}
@@ -1,13 +1,25 @@
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use shaku::Component;
+use sqlx::{Postgres, QueryBuilder};
 use std::sync::Arc;
+use tracing::instrument;
 use uuid::Uuid;
 
-use crate::shared::infrastructure::DatabaseConnection;
+use crate::shared::{
+    domain::{Page, Pagination, SortDirection},
+    infrastructure::{
+        is_unavailable, scoped_connection, DatabaseConnection, PgTransaction,
+        ScopedConnection,
+    },
+};
 
 use crate::features::user::{
-    domain::{User, UserError, UserRepository},
-    infrastructure::models::UserModel,
+    domain::{
+        User, UserError, UserFilters, UserQuery, UserRepository, UserSortField,
+        VerificationToken, VerificationTokenRepository,
+    },
+    infrastructure::models::{UserModel, VerificationTokenModel},
 };
 
 #[derive(Component)]
@@ -15,70 +27,174 @@ use crate::features::user::{
 pub struct PostgresUserRepository {
     #[shaku(inject)]
     database_connection: Arc<dyn DatabaseConnection>,
+    #[shaku(default)]
+    transaction: Option<PgTransaction>,
+}
+
+impl PostgresUserRepository {
+    pub fn in_transaction(
+        database_connection: Arc<dyn DatabaseConnection>,
+        transaction: PgTransaction,
+    ) -> Self {
+        Self {
+            database_connection,
+            transaction: Some(transaction),
+        }
+    }
+
+    async fn connection(&self) -> Result<ScopedConnection, UserError> {
+        scoped_connection(
+            self.database_connection.as_ref(),
+            self.transaction.as_ref(),
+        )
+        .await
+        .map_err(map_db_error)
+    }
 }
 
 #[async_trait]
 impl UserRepository for PostgresUserRepository {
-    async fn find_all(&self) -> Result<Vec<User>, UserError> {
-        let pool = self.database_connection.get_pool();
-        let users = sqlx::query_as::<_, UserModel>(r#"SELECT * FROM users"#)
-            .fetch_all(pool)
+    #[instrument(name = "UserRepository::find_all", skip_all)]
+    async fn find_all(&self, query: &UserQuery) -> Result<Page<User>, UserError> {
+        let mut connection = self.connection().await?;
+
+        let mut count = QueryBuilder::<Postgres>::new("SELECT COUNT(*) FROM users");
+        push_filters(&mut count, &query.filters);
+
+        let total: i64 = count
+            .build_query_scalar()
+            .fetch_one(&mut *connection)
             .await
-            .map_err(|_| UserError::UnexpectedError)?;
+            .map_err(map_db_error)?;
+
+        let column = sort_column(query.sort_by);
+        let (direction, operator) = match query.direction {
+            SortDirection::Asc => ("ASC", ">"),
+            SortDirection::Desc => ("DESC", "<"),
+        };
+
+        let mut select = QueryBuilder::<Postgres>::new("SELECT * FROM users");
+        push_filters(&mut select, &query.filters);
 
-        let entity_vec = users.into_iter().map(|model| User::from(model)).collect();
+        // Keyset pagination: continue right after the (column, id) pair of
+        // the last item returned, the id breaks ties between equal values
 
-        Ok(entity_vec)
+        if let Pagination::Cursor {
+            after: Some(cursor),
+            ..
+        } = &query.pagination
+        {
+            select
+                .push(format!(" AND ({column}, id) {operator} ("))
+                .push_bind(cursor.value.clone())
+                .push(sort_cast(query.sort_by))
+                .push(", ")
+                .push_bind(cursor.id)
+                .push(")");
+        }
+
+        select.push(format!(" ORDER BY {column} {direction}, id {direction}"));
+
+        // One extra row is fetched in cursor mode to know if there is a next page
+
+        match &query.pagination {
+            Pagination::Offset { page, per_page } => {
+                let offset =
+                    i64::from(page.saturating_sub(1)) * i64::from(*per_page);
+                select
+                    .push(" LIMIT ")
+                    .push_bind(i64::from(*per_page))
+                    .push(" OFFSET ")
+                    .push_bind(offset);
+            }
+            Pagination::Cursor { limit, .. } => {
+                select.push(" LIMIT ").push_bind(i64::from(*limit) + 1);
+            }
+        }
+
+        let models = select
+            .build_query_as::<UserModel>()
+            .fetch_all(&mut *connection)
+            .await
+            .map_err(map_db_error)?;
+
+        let mut items: Vec<User> = models.into_iter().map(User::from).collect();
+        let mut next_cursor = None;
+
+        if let Pagination::Cursor { limit, .. } = &query.pagination {
+            if items.len() > *limit as usize {
+                items.truncate(*limit as usize);
+                next_cursor =
+                    items.last().map(|user| query.sort_by.cursor_for(user));
+            }
+        }
+
+        Ok(Page {
+            items,
+            total,
+            next_cursor,
+        })
     }
 
+    #[instrument(name = "UserRepository::find_by_id", skip_all)]
     async fn find_by_id(&self, user_id: Uuid) -> Result<Option<User>, UserError> {
-        let pool = self.database_connection.get_pool();
-        let query = r#"SELECT * FROM users WHERE id = $1"#;
+        let mut connection = self.connection().await?;
+        let query = r#"SELECT * FROM users WHERE id = $1 AND deleted_at IS NULL"#;
 
         let user = sqlx::query_as::<_, UserModel>(query)
             .bind(user_id)
-            .fetch_optional(pool)
+            .fetch_optional(&mut *connection)
             .await
-            .map_err(|_| UserError::UnexpectedError)?;
+            .map_err(map_db_error)?;
 
-        Ok(user.map(|model| User::from(model)))
+        Ok(user.map(User::from))
     }
 
+    #[instrument(name = "UserRepository::find_by_email", skip_all)]
     async fn find_by_email(&self, email: &str) -> Result<Option<User>, UserError> {
-        let pool = self.database_connection.get_pool();
-        let query = r#"SELECT * FROM users WHERE email = $1"#;
+        let mut connection = self.connection().await?;
+        let query = r#"
+            SELECT * FROM users WHERE email = $1::citext AND deleted_at IS NULL
+        "#;
 
         let user = sqlx::query_as::<_, UserModel>(query)
             .bind(email)
-            .fetch_optional(pool)
+            .fetch_optional(&mut *connection)
             .await
-            .map_err(|_| UserError::UnexpectedError)?;
+            .map_err(map_db_error)?;
 
-        Ok(user.map(|model| User::from(model)))
+        Ok(user.map(User::from))
     }
 
+    #[instrument(name = "UserRepository::find_by_username", skip_all)]
     async fn find_by_username(&self, name: &str) -> Result<Option<User>, UserError> {
-        let pool = self.database_connection.get_pool();
-        let query = r#"SELECT * FROM users WHERE username = $1"#;
+        let mut connection = self.connection().await?;
+        let query = r#"
+            SELECT * FROM users
+            WHERE lower(username) = lower($1) AND deleted_at IS NULL
+        "#;
 
         let user = sqlx::query_as::<_, UserModel>(query)
             .bind(name)
-            .fetch_optional(pool)
+            .fetch_optional(&mut *connection)
             .await
-            .map_err(|_| UserError::UnexpectedError)?;
+            .map_err(map_db_error)?;
 
-        Ok(user.map(|model| User::from(model)))
+        Ok(user.map(User::from))
     }
 
+    #[instrument(name = "UserRepository::create", skip_all)]
     async fn create(&self, user: User) -> Result<User, UserError> {
-        let pool = self.database_connection.get_pool();
+        let mut connection = self.connection().await?;
         let query = r#"
             INSERT INTO users (
                 id, username, email, password, validated, created_at, updated_at
             ) VALUES (
                 $1, $2, $3, $4, $5, $6, $7
             )
-            RETURNING id, username, email, password, validated, created_at, updated_at
+            RETURNING
+                id, username, email, password, validated, created_at, updated_at,
+                deleted_at
         "#;
 
         let model = sqlx::query_as::<_, UserModel>(query)
@@ -89,43 +205,447 @@ impl UserRepository for PostgresUserRepository {
             .bind(user.validated)
             .bind(user.created_at)
             .bind(user.updated_at)
-            .fetch_one(pool)
+            .fetch_one(&mut *connection)
             .await
-            .map_err(|_| UserError::UnexpectedError)?;
+            .map_err(map_db_error)?;
 
         Ok(User::from(model))
     }
 
+    #[instrument(name = "UserRepository::update", skip_all)]
     async fn update(&self, user: User) -> Result<User, UserError> {
-        let pool = self.database_connection.get_pool();
+        let mut connection = self.connection().await?;
         let query = r#"
-            UPDATE users 
-            SET username = $1, email = $2, password = $3, updated_at = $4
-            WHERE id = $5
+            UPDATE users
+            SET username = $1, email = $2, password = $3, validated = $4,
+                updated_at = $5
+            WHERE id = $6 AND deleted_at IS NULL
         "#;
 
-        sqlx::query(query)
+        let result = sqlx::query(query)
             .bind(&user.username)
             .bind(&user.email)
             .bind(&user.password)
+            .bind(user.validated)
             .bind(user.updated_at)
             .bind(user.id)
-            .execute(pool)
+            .execute(&mut *connection)
             .await
-            .map_err(|_| UserError::UnexpectedError)?;
+            .map_err(map_db_error)?;
+
+        if result.rows_affected() == 0 {
+            return Err(UserError::NotFound);
+        }
 
         Ok(user)
     }
 
-    async fn delete(&self, user_id: Uuid) -> Result<(), UserError> {
-        let pool = self.database_connection.get_pool();
+    #[instrument(name = "UserRepository::mark_validated", skip_all)]
+    async fn mark_validated(&self, user_id: Uuid) -> Result<User, UserError> {
+        let mut connection = self.connection().await?;
+        let query = r#"
+            UPDATE users SET validated = true, updated_at = now()
+            WHERE id = $1 AND deleted_at IS NULL
+            RETURNING *
+        "#;
+
+        let user = sqlx::query_as::<_, UserModel>(query)
+            .bind(user_id)
+            .fetch_optional(&mut *connection)
+            .await
+            .map_err(map_db_error)?;
+
+        user.map(User::from).ok_or(UserError::NotFound)
+    }
+
+    #[instrument(name = "UserRepository::soft_delete", skip_all)]
+    async fn soft_delete(&self, user_id: Uuid) -> Result<bool, UserError> {
+        let mut connection = self.connection().await?;
+        let query = r#"
+            UPDATE users SET deleted_at = now()
+            WHERE id = $1 AND deleted_at IS NULL
+        "#;
+
+        let result = sqlx::query(query)
+            .bind(user_id)
+            .execute(&mut *connection)
+            .await
+            .map_err(map_db_error)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    #[instrument(name = "UserRepository::restore", skip_all)]
+    async fn restore(&self, user_id: Uuid) -> Result<bool, UserError> {
+        let mut connection = self.connection().await?;
+        let query = r#"
+            UPDATE users SET deleted_at = NULL
+            WHERE id = $1 AND deleted_at IS NOT NULL
+        "#;
+
+        let result = sqlx::query(query)
+            .bind(user_id)
+            .execute(&mut *connection)
+            .await
+            .map_err(map_db_error)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    #[instrument(name = "UserRepository::purge", skip_all)]
+    async fn purge(&self, user_id: Uuid) -> Result<bool, UserError> {
+        let mut connection = self.connection().await?;
+
+        let result = sqlx::query("DELETE FROM users WHERE id = $1")
+            .bind(user_id)
+            .execute(&mut *connection)
+            .await
+            .map_err(map_db_error)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    #[instrument(name = "UserRepository::purge_deleted_before", skip_all)]
+    async fn purge_deleted_before(
+        &self,
+        cutoff: DateTime<Utc>,
+    ) -> Result<u64, UserError> {
+        let mut connection = self.connection().await?;
+        let query = r#"
+            DELETE FROM users
+            WHERE deleted_at IS NOT NULL AND deleted_at < $1
+        "#;
+
+        let result = sqlx::query(query)
+            .bind(cutoff)
+            .execute(&mut *connection)
+            .await
+            .map_err(map_db_error)?;
+
+        Ok(result.rows_affected())
+    }
+}
+
+// Uniqueness is enforced by the database (see the user_uniqueness
+// migration). Use cases may check it first to fail early, but only the
+// constraints hold against concurrent requests, so their violations are
+// mapped the same way. Every other failure keeps the `sqlx::Error` as its
+// cause.
+
+fn map_db_error(error: sqlx::Error) -> UserError {
+    if is_unavailable(&error) {
+        return UserError::DatabaseUnavailable(error.into());
+    }
+
+    match &error {
+        sqlx::Error::Database(db_error) => match db_error.constraint() {
+            Some("users_username_lower_key") if db_error.is_unique_violation() => {
+                UserError::UsernameAlreadyExists
+            }
+            Some("users_email_key") if db_error.is_unique_violation() => {
+                UserError::EmailAlreadyExists
+            }
+            Some(constraint) => UserError::ConstraintViolation {
+                constraint: Some(constraint.to_string()),
+                source: error.into(),
+            },
+            None if db_error.is_unique_violation()
+                || db_error.is_foreign_key_violation()
+                || db_error.is_check_violation() =>
+            {
+                UserError::ConstraintViolation {
+                    constraint: None,
+                    source: error.into(),
+                }
+            }
+            None => UserError::unexpected(error),
+        },
+
+        _ => UserError::unexpected(error),
+    }
+}
+
+// Helpers to build the dynamic `find_all` query. Column names come from
+// the `UserSortField` enum, never from user input, values are always bound.
+
+fn sort_column(field: UserSortField) -> &'static str {
+    match field {
+        UserSortField::CreatedAt => "created_at",
+        UserSortField::UpdatedAt => "updated_at",
+        UserSortField::Username => "username",
+        UserSortField::Email => "email",
+    }
+}
+
+fn sort_cast(field: UserSortField) -> &'static str {
+    match field {
+        UserSortField::CreatedAt | UserSortField::UpdatedAt => "::timestamptz",
+        UserSortField::Username => "::text",
+        UserSortField::Email => "::citext",
+    }
+}
+
+fn push_filters(builder: &mut QueryBuilder<'_, Postgres>, filters: &UserFilters) {
+    builder.push(" WHERE deleted_at IS NULL");
+
+    if let Some(prefix) = &filters.username_prefix {
+        builder
+            .push(" AND username ILIKE ")
+            .push_bind(like_prefix(prefix));
+    }
+
+    if let Some(prefix) = &filters.email_prefix {
+        builder
+            .push(" AND email ILIKE ")
+            .push_bind(like_prefix(prefix));
+    }
+
+    if let Some(validated) = filters.validated {
+        builder.push(" AND validated = ").push_bind(validated);
+    }
+
+    if let Some(from) = filters.created_from {
+        builder.push(" AND created_at >= ").push_bind(from);
+    }
+
+    if let Some(to) = filters.created_to {
+        builder.push(" AND created_at <= ").push_bind(to);
+    }
+}
+
+// Escapes the LIKE wildcards so the prefix is matched literally
+
+fn like_prefix(prefix: &str) -> String {
+    let escaped = prefix
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+
+    format!("{escaped}%")
+}
+
+#[derive(Component)]
+#[shaku(interface = VerificationTokenRepository)]
+pub struct PostgresVerificationTokenRepository {
+    #[shaku(inject)]
+    database_connection: Arc<dyn DatabaseConnection>,
+    #[shaku(default)]
+    transaction: Option<PgTransaction>,
+}
+
+impl PostgresVerificationTokenRepository {
+    pub fn in_transaction(
+        database_connection: Arc<dyn DatabaseConnection>,
+        transaction: PgTransaction,
+    ) -> Self {
+        Self {
+            database_connection,
+            transaction: Some(transaction),
+        }
+    }
+
+    async fn connection(&self) -> Result<ScopedConnection, UserError> {
+        scoped_connection(
+            self.database_connection.as_ref(),
+            self.transaction.as_ref(),
+        )
+        .await
+        .map_err(map_db_error)
+    }
+}
+
+#[async_trait]
+impl VerificationTokenRepository for PostgresVerificationTokenRepository {
+    #[instrument(name = "VerificationTokenRepository::find_by_hash", skip_all)]
+    async fn find_by_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<VerificationToken>, UserError> {
+        let mut connection = self.connection().await?;
+        let query =
+            r#"SELECT * FROM email_verification_tokens WHERE token_hash = $1"#;
+
+        let token = sqlx::query_as::<_, VerificationTokenModel>(query)
+            .bind(token_hash)
+            .fetch_optional(&mut *connection)
+            .await
+            .map_err(map_db_error)?;
+
+        Ok(token.map(VerificationToken::from))
+    }
+
+    #[instrument(name = "VerificationTokenRepository::create", skip_all)]
+    async fn create(
+        &self,
+        token: VerificationToken,
+    ) -> Result<VerificationToken, UserError> {
+        let mut connection = self.connection().await?;
+        let query = r#"
+            INSERT INTO email_verification_tokens (
+                id, user_id, token_hash, expires_at, used_at, created_at
+            ) VALUES (
+                $1, $2, $3, $4, $5, $6
+            )
+            RETURNING id, user_id, token_hash, expires_at, used_at, created_at
+        "#;
+
+        let model = sqlx::query_as::<_, VerificationTokenModel>(query)
+            .bind(token.id)
+            .bind(token.user_id)
+            .bind(token.token_hash)
+            .bind(token.expires_at)
+            .bind(token.used_at)
+            .bind(token.created_at)
+            .fetch_one(&mut *connection)
+            .await
+            .map_err(map_db_error)?;
+
+        Ok(VerificationToken::from(model))
+    }
+
+    #[instrument(name = "VerificationTokenRepository::mark_used", skip_all)]
+    async fn mark_used(&self, id: Uuid) -> Result<bool, UserError> {
+        let mut connection = self.connection().await?;
+        let query = r#"
+            UPDATE email_verification_tokens SET used_at = now()
+            WHERE id = $1 AND used_at IS NULL
+        "#;
 
-        sqlx::query("DELETE FROM users WHERE id = $1")
+        let result = sqlx::query(query)
+            .bind(id)
+            .execute(&mut *connection)
+            .await
+            .map_err(map_db_error)?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    #[instrument(name = "VerificationTokenRepository::delete_by_user", skip_all)]
+    async fn delete_by_user(&self, user_id: Uuid) -> Result<(), UserError> {
+        let mut connection = self.connection().await?;
+
+        sqlx::query("DELETE FROM email_verification_tokens WHERE user_id = $1")
             .bind(user_id)
-            .execute(pool)
+            .execute(&mut *connection)
             .await
-            .map_err(|_| UserError::UnexpectedError)?;
+            .map_err(map_db_error)?;
 
         Ok(())
     }
 }
+
+// Run against Postgres, they are ignored by default (see
+// `shared::infrastructure::testing::with_test_database`)
+
+#[cfg(test)]
+mod tests {
+    use shaku::HasComponent;
+
+    use super::*;
+    use crate::features::user::{domain::UserUnitOfWork, infrastructure::contract};
+    use crate::shared::infrastructure::testing::{user, with_test_database};
+
+    #[tokio::test]
+    #[ignore = "needs Postgres, see with_test_database"]
+    async fn usernames_and_emails_are_unique_ignoring_case() {
+        with_test_database(|state| async move {
+            let repository: &dyn UserRepository = state.module.resolve_ref();
+            contract::usernames_and_emails_are_unique_ignoring_case(repository)
+                .await;
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    #[ignore = "needs Postgres, see with_test_database"]
+    async fn soft_deleted_users_are_hidden_until_restored() {
+        with_test_database(|state| async move {
+            let repository: &dyn UserRepository = state.module.resolve_ref();
+            contract::soft_deleted_users_are_hidden_until_restored(repository).await;
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    #[ignore = "needs Postgres, see with_test_database"]
+    async fn cursor_pagination_walks_every_page() {
+        with_test_database(|state| async move {
+            let repository: &dyn UserRepository = state.module.resolve_ref();
+            contract::cursor_pagination_walks_every_page(repository).await;
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    #[ignore = "needs Postgres, see with_test_database"]
+    async fn updates_only_reach_active_users() {
+        with_test_database(|state| async move {
+            let repository: &dyn UserRepository = state.module.resolve_ref();
+            contract::updates_only_reach_active_users(repository).await;
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    #[ignore = "needs Postgres, see with_test_database"]
+    async fn only_committed_writes_are_kept() {
+        with_test_database(|state| async move {
+            let unit_of_work: &dyn UserUnitOfWork = state.module.resolve_ref();
+            let repository: &dyn UserRepository = state.module.resolve_ref();
+            contract::only_committed_writes_are_kept(unit_of_work, repository).await;
+        })
+        .await;
+    }
+
+    // Specific to Postgres: the other constraints and the isolation of
+    // the transactions (the in-memory ones apply their writes right away)
+
+    #[tokio::test]
+    #[ignore = "needs Postgres, see with_test_database"]
+    async fn maps_other_constraint_violations() {
+        with_test_database(|state| async move {
+            let repository: &dyn UserRepository = state.module.resolve_ref();
+            let alice = repository
+                .create(user("alice", "alice@example.com"))
+                .await
+                .unwrap();
+
+            let id = repository
+                .create(User {
+                    id: alice.id,
+                    ..user("carol", "carol@example.com")
+                })
+                .await;
+
+            assert!(matches!(
+                id,
+                Err(UserError::ConstraintViolation {
+                    constraint: Some(constraint),
+                    ..
+                }) if constraint == "users_pkey"
+            ));
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    #[ignore = "needs Postgres, see with_test_database"]
+    async fn uncommitted_writes_are_not_visible_outside_the_transaction() {
+        with_test_database(|state| async move {
+            let unit_of_work: &dyn UserUnitOfWork = state.module.resolve_ref();
+            let repository: &dyn UserRepository = state.module.resolve_ref();
+
+            let tx = unit_of_work.begin().await.unwrap();
+            let alice = tx
+                .users()
+                .create(user("alice", "alice@example.com"))
+                .await
+                .unwrap();
+
+            assert!(repository.find_by_id(alice.id).await.unwrap().is_none());
+            tx.commit().await.unwrap();
+            assert!(repository.find_by_id(alice.id).await.unwrap().is_some());
+        })
+        .await;
+    }
+}
@@ -1,13 +1,184 @@
+use axum::middleware::from_fn_with_state;
 use axum::routing::{delete, get, patch, post, Router};
 
 use super::controllers::*;
-use crate::shared::infrastructure::AppState;
+use crate::features::role::domain::permissions::{USERS_LIST, USERS_PURGE};
+use crate::shared::infrastructure::{
+    guards::{require_auth, require_permission},
+    AppState,
+};
 
 pub fn router(state: AppState) -> Router {
-    Router::new()
-        .route("/users", get(get_users))
-        .route("/users/", post(create_user))
+    // Routes that can only be invoked with a valid access token
+
+    let protected = Router::new()
         .route("/users/{id}", patch(update_user))
         .route("/users/{id}", delete(delete_user))
+        .route("/users/{id}/restore", post(restore_user))
+        .route_layer(from_fn_with_state(state.clone(), require_auth));
+
+    // The listing exposes every email and can be filtered by their prefix,
+    // so it is restricted to admins like purging, which can't be undone
+
+    let listing = Router::new().route("/users", get(get_users)).route_layer(
+        from_fn_with_state(state.clone(), require_permission(USERS_LIST)),
+    );
+
+    let admin = Router::new()
+        .route("/users/{id}/purge", delete(purge_user))
+        .route_layer(from_fn_with_state(
+            state.clone(),
+            require_permission(USERS_PURGE),
+        ));
+
+    Router::new()
+        .route("/users/", post(create_user))
+        .route("/users/{id}", get(get_user_by_id))
+        .route("/users/verify", get(verify_email))
+        .route("/users/verify/resend", post(resend_verification))
+        .merge(protected)
+        .merge(listing)
+        .merge(admin)
         .with_state(state)
 }
+
+#[cfg(test)]
+mod tests {
+    use axum::http::{Method, StatusCode};
+    use serde_json::{json, Value};
+
+    use crate::shared::infrastructure::testing::{
+        get_request, json_request, TestModuleBuilder,
+    };
+
+    fn new_user(username: &str, email: &str) -> Value {
+        json!({
+            "username": username,
+            "email": email,
+            "password": "Secret123!",
+            "confirmPassword": "Secret123!",
+        })
+    }
+
+    #[tokio::test]
+    async fn creates_and_fetches_a_user() {
+        let app = TestModuleBuilder::new().build();
+
+        let (status, body) = app
+            .send(json_request(
+                Method::POST,
+                "/users/",
+                new_user("alice", "alice@example.com"),
+            ))
+            .await;
+
+        assert_eq!(status, StatusCode::CREATED);
+        assert!(body["data"].get("password").is_none());
+
+        let id = body["data"]["id"].as_str().unwrap();
+        let (status, body) = app.send(get_request(&format!("/users/{id}"))).await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["data"]["username"], "alice");
+        assert_eq!(body["data"]["validated"], false);
+    }
+
+    #[tokio::test]
+    async fn rejects_a_duplicated_email_with_a_problem() {
+        let app = TestModuleBuilder::new().build();
+        let create = |username, email| {
+            json_request(Method::POST, "/users/", new_user(username, email))
+        };
+
+        app.send(create("alice", "alice@example.com")).await;
+        let (status, body) = app.send(create("alice2", "ALICE@example.com")).await;
+
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert_eq!(body["code"], "user.email_taken");
+        assert_eq!(body["field"], "email");
+        assert_eq!(body["instance"], "/users/");
+        assert!(body["requestId"].is_string());
+    }
+
+    #[tokio::test]
+    async fn reports_every_invalid_field() {
+        let app = TestModuleBuilder::new().build();
+        let user = new_user("al", "not-an-email");
+
+        let (status, body) =
+            app.send(json_request(Method::POST, "/users/", user)).await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["code"], "request.invalid_body");
+        assert_eq!(body["errors"]["username"][0]["code"], "length");
+        assert_eq!(body["errors"]["email"][0]["code"], "email");
+    }
+
+    #[tokio::test]
+    async fn anonymous_clients_can_not_list_the_users() {
+        let app = TestModuleBuilder::new().build();
+
+        let (status, body) = app.send(get_request("/users?email=a")).await;
+
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(body["code"], "auth.missing_token");
+    }
+
+    #[tokio::test]
+    async fn unknown_users_are_not_found() {
+        let app = TestModuleBuilder::new().build();
+        let id = uuid::Uuid::new_v4();
+
+        let (status, body) = app.send(get_request(&format!("/users/{id}"))).await;
+
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body["code"], "user.not_found");
+    }
+
+    #[tokio::test]
+    async fn selects_fields_by_their_camel_case_name() {
+        let app = TestModuleBuilder::new().build();
+        let create = |fields: &str| {
+            json_request(
+                Method::POST,
+                &format!("/users/?fields={fields}"),
+                new_user("alice", "alice@example.com"),
+            )
+        };
+
+        let (status, body) = app.send(create("created_at")).await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["errors"]["fields"][0]["code"], "unknown_field");
+
+        let (status, body) = app.send(create("id,createdAt")).await;
+        let data = body["data"].as_object().unwrap();
+
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(data.keys().collect::<Vec<_>>(), ["createdAt", "id"]);
+    }
+
+    #[tokio::test]
+    async fn answers_a_resend_the_same_when_the_email_cannot_be_sent() {
+        let app = TestModuleBuilder::new().build();
+
+        app.send(json_request(
+            Method::POST,
+            "/users/",
+            new_user("alice", "alice@example.com"),
+        ))
+        .await;
+        app.mailer.fail_sends(true);
+
+        let (status, _) = app
+            .send(json_request(
+                Method::POST,
+                "/users/verify/resend",
+                json!({ "email": "alice@example.com" }),
+            ))
+            .await;
+
+        assert_eq!(status, StatusCode::ACCEPTED);
+        assert_eq!(app.mailer.sent().len(), 1);
+    }
+}
@@ -0,0 +1,83 @@
+use async_trait::async_trait;
+use shaku::Component;
+use std::sync::Arc;
+
+use super::repository::{
+    PostgresUserRepository, PostgresVerificationTokenRepository,
+};
+use crate::features::user::domain::{
+    UserRepository, UserTransaction, UserUnitOfWork, VerificationTokenRepository,
+};
+use crate::shared::{
+    domain::{Transaction, TransactionError},
+    infrastructure::{DatabaseConnection, PgTransaction},
+};
+
+#[derive(Component)]
+#[shaku(interface = UserUnitOfWork)]
+pub struct PostgresUserUnitOfWork {
+    #[shaku(inject)]
+    database_connection: Arc<dyn DatabaseConnection>,
+}
+
+#[async_trait]
+impl UserUnitOfWork for PostgresUserUnitOfWork {
+    async fn begin(&self) -> Result<Box<dyn UserTransaction>, TransactionError> {
+        let database = &self.database_connection;
+        let transaction = PgTransaction::begin(database.get_pool()).await?;
+
+        Ok(Box::new(PostgresUserTransaction {
+            users: PostgresUserRepository::in_transaction(
+                database.clone(),
+                transaction.clone(),
+            ),
+            verification_tokens: PostgresVerificationTokenRepository::in_transaction(
+                database.clone(),
+                transaction.clone(),
+            ),
+            transaction,
+        }))
+    }
+}
+
+struct PostgresUserTransaction {
+    transaction: PgTransaction,
+    users: PostgresUserRepository,
+    verification_tokens: PostgresVerificationTokenRepository,
+}
+
+impl PostgresUserTransaction {
+    // Drops the repositories, so the transaction can be finished
+
+    fn finish(self) -> PgTransaction {
+        let Self {
+            transaction,
+            users,
+            verification_tokens,
+        } = self;
+        drop((users, verification_tokens));
+
+        transaction
+    }
+}
+
+#[async_trait]
+impl Transaction for PostgresUserTransaction {
+    async fn commit(self: Box<Self>) -> Result<(), TransactionError> {
+        self.finish().commit().await
+    }
+
+    async fn rollback(self: Box<Self>) -> Result<(), TransactionError> {
+        self.finish().rollback().await
+    }
+}
+
+impl UserTransaction for PostgresUserTransaction {
+    fn users(&self) -> &dyn UserRepository {
+        &self.users
+    }
+
+    fn verification_tokens(&self) -> &dyn VerificationTokenRepository {
+        &self.verification_tokens
+    }
+}
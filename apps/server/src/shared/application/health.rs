@@ -0,0 +1,44 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde_json::{Map, Value};
+use shaku::Interface;
+
+// Dependencies the application needs to serve requests (database, ...)
+// register a check, `/health/ready` runs all of them and reports 503
+// when any is down. `/health/live` never runs them.
+
+pub struct CheckResult {
+    pub healthy: bool,
+    pub details: Map<String, Value>,
+}
+
+impl CheckResult {
+    pub fn up(details: Map<String, Value>) -> Self {
+        Self {
+            healthy: true,
+            details,
+        }
+    }
+
+    pub fn down(error: impl ToString) -> Self {
+        let mut details = Map::new();
+        details.insert("error".to_string(), Value::String(error.to_string()));
+
+        Self {
+            healthy: false,
+            details,
+        }
+    }
+}
+
+#[async_trait]
+pub trait HealthCheck: Send + Sync {
+    fn name(&self) -> &str;
+    async fn check(&self) -> CheckResult;
+}
+
+pub trait HealthChecks: Interface {
+    fn register(&self, check: Arc<dyn HealthCheck>);
+    fn all(&self) -> Vec<Arc<dyn HealthCheck>>;
+}
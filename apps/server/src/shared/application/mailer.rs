@@ -0,0 +1,46 @@
+use std::fmt;
+
+use async_trait::async_trait;
+use shaku::Interface;
+
+// An outgoing email, the body is sent as plain text.
+
+#[derive(Debug, Clone)]
+pub struct Email {
+    pub to: String,
+    pub subject: String,
+    pub body: String,
+}
+
+// A mailer that can't be built from the settings is `Misconfigured`, it
+// is reported at startup instead of as a failed delivery
+
+#[derive(Debug)]
+pub enum MailError {
+    SendFailed(String),
+    Misconfigured(String),
+}
+
+impl fmt::Display for MailError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MailError::SendFailed(reason) => {
+                write!(f, "failed to send email: {reason}")
+            }
+            MailError::Misconfigured(reason) => {
+                write!(f, "invalid mailer configuration: {reason}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for MailError {}
+
+// Application layer contract to send emails, use cases depend on this
+// interface so the delivery mechanism can be swapped (e.g. in tests).
+// implementations in: /shared/infrastructure/mailer.rs
+
+#[async_trait]
+pub trait Mailer: Interface {
+    async fn send(&self, email: Email) -> Result<(), MailError>;
+}
@@ -0,0 +1,9 @@
+mod health;
+mod mailer;
+mod shutdown;
+mod tokens;
+
+pub use health::*;
+pub use mailer::*;
+pub use shutdown::*;
+pub use tokens::*;
@@ -0,0 +1,26 @@
+use std::{future::Future, pin::Pin};
+
+use shaku::Interface;
+
+// Components that own resources (pools, background jobs, ...) register
+// a hook to release them when the server stops. Hooks run once, after
+// the in-flight requests were drained, in reverse registration order.
+
+pub type ShutdownFuture = Pin<Box<dyn Future<Output = ()> + Send>>;
+pub type ShutdownHook = Box<dyn FnOnce() -> ShutdownFuture + Send>;
+
+pub trait ShutdownHooks: Interface {
+    fn register(&self, name: &str, hook: ShutdownHook);
+    fn take_all(&self) -> Vec<(String, ShutdownHook)>;
+}
+
+// Wraps an async closure into a `ShutdownHook`, e.g:
+// hooks.register("database", shutdown_hook(move || async move { pool.close().await }));
+
+pub fn shutdown_hook<F, Fut>(hook: F) -> ShutdownHook
+where
+    F: FnOnce() -> Fut + Send + 'static,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    Box::new(move || Box::pin(hook()))
+}
@@ -0,0 +1,14 @@
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+// Helpers for single-use tokens sent by email (verification links,
+// password resets...). Only the hash of a token is stored, so a leaked
+// database dump can't be used to take over accounts.
+
+pub fn generate_secure_token() -> String {
+    format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple())
+}
+
+pub fn hash_token(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
+}
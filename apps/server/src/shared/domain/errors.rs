@@ -0,0 +1,22 @@
+use std::error::Error;
+
+// Cause of an unexpected failure (e.g. a `sqlx::Error`), kept by the
+// domain errors so it can be logged without the domain depending on the
+// crates that produced it.
+
+pub type BoxError = Box<dyn Error + Send + Sync>;
+
+// "outer: inner: root", the whole `source()` chain in a single line
+
+pub fn error_chain(error: &dyn Error) -> String {
+    let mut chain = error.to_string();
+    let mut source = error.source();
+
+    while let Some(cause) = source {
+        chain.push_str(": ");
+        chain.push_str(&cause.to_string());
+        source = cause.source();
+    }
+
+    chain
+}
@@ -0,0 +1,7 @@
+mod errors;
+mod pagination;
+mod transaction;
+
+pub use errors::*;
+pub use pagination::*;
+pub use transaction::*;
@@ -0,0 +1,50 @@
+use uuid::Uuid;
+
+// Pagination primitives shared by every feature that lists entities.
+
+// |---------------------------------------------------------------|
+// |   Strategy   |            Request            |    Response     |
+// |---------------------------------------------------------------|
+// |    Offset    |  page (1-based) + per_page    |  total, pages   |
+// |    Cursor    |  after (last cursor) + limit  |  next_cursor    |
+// |---------------------------------------------------------------|
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortDirection {
+    Asc,
+    Desc,
+}
+
+// Keyset position of the last item of a page: the value of the sort
+// column (as text) and the id of the item, used as a tie breaker.
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cursor {
+    pub value: String,
+    pub id: Uuid,
+}
+
+#[derive(Debug, Clone)]
+pub enum Pagination {
+    Offset { page: u32, per_page: u32 },
+    Cursor { after: Option<Cursor>, limit: u32 },
+}
+
+impl Pagination {
+    pub fn limit(&self) -> u32 {
+        match self {
+            Pagination::Offset { per_page, .. } => *per_page,
+            Pagination::Cursor { limit, .. } => *limit,
+        }
+    }
+}
+
+// A page of results. `total` counts every item matching the filters,
+// `next_cursor` is only present when there are more items to fetch.
+
+#[derive(Debug, Clone)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub total: i64,
+    pub next_cursor: Option<Cursor>,
+}
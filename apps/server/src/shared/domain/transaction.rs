@@ -0,0 +1,53 @@
+use std::{error::Error, fmt};
+
+use async_trait::async_trait;
+
+use super::BoxError;
+
+// A unit of work groups the writes of a use case in a single database
+// transaction, so either all of them are applied or none. Each feature
+// declares its own unit of work, whose transaction hands out the
+// repositories bound to it:
+
+// let tx = self.unit_of_work.begin().await?;
+// tx.verification_tokens().mark_used(token.id).await?;
+// tx.users().update(user).await?;
+// tx.commit().await?;
+
+// Returning before `commit` (e.g. with `?`) drops the transaction, which
+// rolls it back. The repositories borrow it, so they can't outlive it.
+
+// units of work in: /features/user/domain/transaction.rs and
+// /features/auth/domain/transaction.rs
+
+#[async_trait]
+pub trait Transaction: Send + Sync {
+    async fn commit(self: Box<Self>) -> Result<(), TransactionError>;
+    async fn rollback(self: Box<Self>) -> Result<(), TransactionError>;
+}
+
+#[derive(Debug)]
+pub enum TransactionError {
+    // No connection could be acquired (same causes as the
+    // `DatabaseUnavailable` errors of the features)
+    Unavailable(BoxError),
+    Failed(BoxError),
+}
+
+impl fmt::Display for TransactionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TransactionError::Unavailable(_) => write!(f, "database unavailable"),
+            TransactionError::Failed(_) => write!(f, "transaction failed"),
+        }
+    }
+}
+
+impl Error for TransactionError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            TransactionError::Unavailable(source)
+            | TransactionError::Failed(source) => Some(source.as_ref()),
+        }
+    }
+}
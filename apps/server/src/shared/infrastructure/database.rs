@@ -1,9 +1,16 @@
-use std::time::Duration;
+use std::{
+    ops::{Deref, DerefMut},
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use shaku::{Component, Interface};
-use sqlx::{postgres::PgPoolOptions, PgPool};
+use sqlx::{
+    pool::PoolConnection, postgres::PgPoolOptions, PgConnection, PgPool, Postgres,
+};
+use tokio::sync::{Mutex, OwnedMutexGuard};
 
-use crate::shared::constants::POSTGRES_DATABASE_URL;
+use crate::shared::{domain::TransactionError, settings::DatabaseSettings};
 
 pub trait DatabaseConnection: Interface {
     fn get_pool(&self) -> &PgPool;
@@ -16,12 +23,12 @@ pub struct PostgresDatabase {
 }
 
 impl PostgresDatabase {
-    pub async fn new() -> Result<Self, sqlx::Error> {
+    pub async fn new(settings: &DatabaseSettings) -> Result<Self, sqlx::Error> {
         let pool = PgPoolOptions::new()
-            .min_connections(1)
-            .max_connections(10)
-            .acquire_timeout(Duration::from_secs(5))
-            .connect(&POSTGRES_DATABASE_URL)
+            .min_connections(settings.min_connections)
+            .max_connections(settings.max_connections)
+            .acquire_timeout(Duration::from_secs(settings.acquire_timeout))
+            .connect(&settings.url)
             .await?;
 
         Ok(Self { pool })
@@ -32,7 +39,7 @@ impl PostgresDatabase {
             .run(&self.pool)
             .await?;
 
-        println!("Database migrations completed successfully.");
+        tracing::info!("Database migrations completed successfully");
 
         Ok(())
     }
@@ -44,8 +51,133 @@ impl DatabaseConnection for PostgresDatabase {
     }
 }
 
-impl Into<PostgresDatabaseParameters> for PostgresDatabase {
-    fn into(self) -> PostgresDatabaseParameters {
-        PostgresDatabaseParameters { pool: self.pool }
+impl From<PostgresDatabase> for PostgresDatabaseParameters {
+    fn from(database: PostgresDatabase) -> Self {
+        PostgresDatabaseParameters {
+            pool: database.pool,
+        }
+    }
+}
+
+// Transaction of a Postgres unit of work. It holds a connection until it
+// is committed or rolled back, sqlx rolls back the ones dropped without
+// being committed when their connection returns to the pool. The
+// repositories bound to it share the handle.
+
+#[derive(Clone)]
+pub struct PgTransaction(Arc<Mutex<sqlx::Transaction<'static, Postgres>>>);
+
+impl PgTransaction {
+    pub async fn begin(pool: &PgPool) -> Result<Self, TransactionError> {
+        let connection = acquire(pool).await.map_err(transaction_error)?;
+        let transaction = sqlx::Transaction::begin(connection, None)
+            .await
+            .map_err(transaction_error)?;
+
+        Ok(Self(Arc::new(Mutex::new(transaction))))
+    }
+
+    // The repositories bound to the transaction must be dropped first,
+    // its owner finishes it once it is the only handle left
+
+    pub async fn commit(self) -> Result<(), TransactionError> {
+        self.into_inner()?.commit().await.map_err(transaction_error)
+    }
+
+    pub async fn rollback(self) -> Result<(), TransactionError> {
+        self.into_inner()?
+            .rollback()
+            .await
+            .map_err(transaction_error)
+    }
+
+    fn into_inner(
+        self,
+    ) -> Result<sqlx::Transaction<'static, Postgres>, TransactionError> {
+        Arc::into_inner(self.0)
+            .map(Mutex::into_inner)
+            .ok_or_else(|| {
+                TransactionError::Failed("transaction still in use".into())
+            })
+    }
+}
+
+// Connection a Postgres repository runs a query on, e.g:
+
+// let mut connection = scoped_connection(database, transaction).await?;
+// sqlx::query(query).execute(&mut *connection).await
+
+// Repositories resolved from the module have no transaction and take a
+// connection from the pool, the ones handed out by a unit of work use
+// the connection of its transaction.
+
+pub enum ScopedConnection {
+    Pool(PoolConnection<Postgres>),
+    Transaction(OwnedMutexGuard<sqlx::Transaction<'static, Postgres>>),
+}
+
+pub async fn scoped_connection(
+    database: &dyn DatabaseConnection,
+    transaction: Option<&PgTransaction>,
+) -> Result<ScopedConnection, sqlx::Error> {
+    match transaction {
+        Some(transaction) => Ok(ScopedConnection::Transaction(
+            transaction.0.clone().lock_owned().await,
+        )),
+        None => Ok(ScopedConnection::Pool(acquire(database.get_pool()).await?)),
+    }
+}
+
+impl Deref for ScopedConnection {
+    type Target = PgConnection;
+
+    fn deref(&self) -> &PgConnection {
+        match self {
+            ScopedConnection::Pool(connection) => connection,
+            ScopedConnection::Transaction(transaction) => transaction,
+        }
+    }
+}
+
+impl DerefMut for ScopedConnection {
+    fn deref_mut(&mut self) -> &mut PgConnection {
+        match self {
+            ScopedConnection::Pool(connection) => connection,
+            ScopedConnection::Transaction(transaction) => transaction,
+        }
+    }
+}
+
+// sqlx doesn't expose how many tasks are waiting for a connection, so the
+// time each acquire waited (timeouts included) is recorded instead
+
+async fn acquire(pool: &PgPool) -> Result<PoolConnection<Postgres>, sqlx::Error> {
+    let start = Instant::now();
+    let connection = pool.acquire().await;
+
+    metrics::histogram!("db_pool_acquire_duration_seconds")
+        .record(start.elapsed().as_secs_f64());
+
+    connection
+}
+
+// The database could not be reached in time (pool timeout, closed pool,
+// network errors), the repositories report it apart from other failures
+
+pub fn is_unavailable(error: &sqlx::Error) -> bool {
+    matches!(
+        error,
+        sqlx::Error::PoolTimedOut
+            | sqlx::Error::PoolClosed
+            | sqlx::Error::Io(_)
+            | sqlx::Error::Tls(_)
+    )
+}
+
+fn transaction_error(error: sqlx::Error) -> TransactionError {
+    if is_unavailable(&error) {
+        TransactionError::Unavailable(error.into())
+    } else {
+        TransactionError::Failed(error.into())
     }
 }
@@ -3,21 +3,49 @@ use shaku::module;
 use std::sync::Arc;
 
 use crate::{
+    features::auth::{
+        application::{
+            services::{JwtTokenService, SessionServiceImpl},
+            usecases::{
+                ForgotPasswordCaseImpl, LoginCaseImpl, RefreshTokenCaseImpl,
+                ResetPasswordCaseImpl,
+            },
+        },
+        infrastructure::{
+            PostgresAuthUnitOfWork, PostgresPasswordResetTokenRepository,
+            PostgresRefreshTokenRepository,
+        },
+    },
+    features::role::{
+        application::usecases::{
+            GetRolesCaseImpl, GrantRoleCaseImpl, RevokeRoleCaseImpl,
+        },
+        infrastructure::PostgresRoleRepository,
+    },
     features::user::{
         application::{
-            services::BcryptPasswordHasher,
+            services::{BcryptPasswordHasher, VerificationServiceImpl},
             usecases::{
-                CreateUserCaseImpl, DeleteUserCaseImpl, GetUsersCaseImpl,
-                UpdateUserCaseImpl,
+                CreateUserCaseImpl, DeleteUserCaseImpl, GetUserByIdCaseImpl,
+                GetUsersCaseImpl, PurgeDeletedUsersCaseImpl, PurgeUserCaseImpl,
+                ResendVerificationCaseImpl, RestoreUserCaseImpl, UpdateUserCaseImpl,
+                VerifyEmailCaseImpl,
             },
         },
-        infrastructure::PostgresUserRepository,
+        infrastructure::{
+            PostgresUserRepository, PostgresUserUnitOfWork,
+            PostgresVerificationTokenRepository,
+        },
+    },
+    shared::{
+        infrastructure::{
+            database::PostgresDatabase, HealthRegistry, InMemoryMailer,
+            ShutdownRegistry,
+        },
+        settings::AppSettings,
     },
-    shared::infrastructure::database::PostgresDatabase,
 };
 
-pub type Inject<T> = shaku_axum::Inject<AppModule, T>;
-
 #[derive(Clone)]
 pub struct AppState {
     pub module: Arc<AppModule>,
@@ -33,15 +61,45 @@ module! {
 
     pub AppModule {
         components = [
+            AppSettings,
             PostgresDatabase,
+            PostgresUserUnitOfWork,
             PostgresUserRepository,
+            PostgresVerificationTokenRepository,
 
+            InMemoryMailer,
+            ShutdownRegistry,
+            HealthRegistry,
             BcryptPasswordHasher,
+            VerificationServiceImpl,
 
             GetUsersCaseImpl,
+            GetUserByIdCaseImpl,
             CreateUserCaseImpl,
             UpdateUserCaseImpl,
-            DeleteUserCaseImpl
+            DeleteUserCaseImpl,
+            RestoreUserCaseImpl,
+            PurgeUserCaseImpl,
+            PurgeDeletedUsersCaseImpl,
+            VerifyEmailCaseImpl,
+            ResendVerificationCaseImpl,
+
+            PostgresAuthUnitOfWork,
+            PostgresRefreshTokenRepository,
+            PostgresPasswordResetTokenRepository,
+            PostgresRoleRepository,
+
+            JwtTokenService,
+            SessionServiceImpl,
+
+            LoginCaseImpl,
+            RefreshTokenCaseImpl,
+            ForgotPasswordCaseImpl,
+            ResetPasswordCaseImpl,
+
+            GetRolesCaseImpl,
+            GrantRoleCaseImpl,
+            RevokeRoleCaseImpl
         ],
         providers = []
     }
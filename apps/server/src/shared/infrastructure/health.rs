@@ -0,0 +1,110 @@
+use std::{
+    sync::{Arc, RwLock},
+    time::Instant,
+};
+
+use async_trait::async_trait;
+use serde_json::{json, Map};
+use shaku::Component;
+use sqlx::PgPool;
+
+use crate::shared::application::{CheckResult, HealthCheck, HealthChecks};
+
+#[derive(Component, Default)]
+#[shaku(interface = HealthChecks)]
+pub struct HealthRegistry {
+    #[shaku(default)]
+    checks: RwLock<Vec<Arc<dyn HealthCheck>>>,
+}
+
+impl HealthChecks for HealthRegistry {
+    fn register(&self, check: Arc<dyn HealthCheck>) {
+        if let Ok(mut checks) = self.checks.write() {
+            checks.push(check);
+        }
+    }
+
+    fn all(&self) -> Vec<Arc<dyn HealthCheck>> {
+        self.checks
+            .read()
+            .map(|checks| checks.clone())
+            .unwrap_or_default()
+    }
+}
+
+// Pings Postgres and reports how long the ping took, the applied
+// migration and the pool usage.
+// The database is considered down when it can't be reached or when it
+// is behind the migrations embedded in the binary.
+
+pub struct DatabaseHealthCheck {
+    pool: PgPool,
+}
+
+impl DatabaseHealthCheck {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl HealthCheck for DatabaseHealthCheck {
+    fn name(&self) -> &str {
+        "database"
+    }
+
+    async fn check(&self) -> CheckResult {
+        let start = Instant::now();
+
+        if let Err(e) = sqlx::query("SELECT 1").execute(&self.pool).await {
+            return CheckResult::down(e);
+        }
+
+        let latency = start.elapsed();
+
+        let query = r#"
+            SELECT MAX(version) FROM _sqlx_migrations WHERE success = TRUE
+        "#;
+
+        let applied: Option<i64> =
+            match sqlx::query_scalar(query).fetch_one(&self.pool).await {
+                Ok(version) => version,
+                Err(e) => return CheckResult::down(e),
+            };
+
+        let expected = sqlx::migrate!("./config/migrations")
+            .iter()
+            .map(|migration| migration.version)
+            .max();
+
+        let max = self.pool.options().get_max_connections();
+        let size = self.pool.size();
+        let idle = self.pool.num_idle() as u32;
+        let in_use = size.saturating_sub(idle);
+
+        let mut details = Map::new();
+        details.insert(
+            "latencyMs".to_string(),
+            json!(latency.as_secs_f64() * 1000.0),
+        );
+        details.insert(
+            "migrations".to_string(),
+            json!({ "applied": applied, "expected": expected }),
+        );
+        details.insert(
+            "pool".to_string(),
+            json!({
+                "size": size,
+                "idle": idle,
+                "inUse": in_use,
+                "max": max,
+                "saturation": f64::from(in_use) / f64::from(max.max(1)),
+            }),
+        );
+
+        CheckResult {
+            healthy: applied >= expected,
+            details,
+        }
+    }
+}
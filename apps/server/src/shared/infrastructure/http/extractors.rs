@@ -1,35 +1,77 @@
-use std::ops::Deref;
+use std::{convert::Infallible, future::Future, ops::Deref, sync::Arc};
 
-use axum::extract::{FromRequest, Json, Query, Request};
+use axum::extract::{FromRef, FromRequest, FromRequestParts, Json, Query, Request};
+use axum::http::{header::AUTHORIZATION, request::Parts};
 
 use axum_responses::http::HttpResponse;
-use serde_json::json;
+use shaku::{HasComponent, Interface};
+use uuid::Uuid;
 use validator::Validate;
 
+use super::metrics::observe;
+use super::validation::{
+    json_rejection_problem, query_rejection_problem, validation_problem,
+};
+use crate::features::auth::{
+    application::services::{TokenKind, TokenService},
+    domain::AuthError,
+};
+use crate::shared::infrastructure::AppModule;
+
+// The use case run by the handler, resolved from the module. It can only
+// be executed through `run`, so every call is observed (see `observe`),
+// e.g. `use_case.run(|use_case| use_case.execute(id)).await?`. Requests
+// rejected before the call (e.g. an invalid body) are not counted.
+
+pub struct UseCase<T: ?Sized>(Arc<T>);
+
+impl<T: ?Sized> UseCase<T> {
+    pub async fn run<'a, F, O, E>(
+        &'a self,
+        execute: impl FnOnce(&'a T) -> F,
+    ) -> Result<O, E>
+    where
+        F: Future<Output = Result<O, E>>,
+    {
+        observe(&*self.0, execute).await
+    }
+}
+
+impl<S, T> FromRequestParts<S> for UseCase<T>
+where
+    S: Send + Sync,
+    T: Interface + ?Sized,
+    Arc<AppModule>: FromRef<S>,
+    AppModule: HasComponent<T>,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(
+        _: &mut Parts,
+        state: &S,
+    ) -> Result<Self, Self::Rejection> {
+        let module = Arc::<AppModule>::from_ref(state);
+
+        Ok(UseCase(module.resolve()))
+    }
+}
+
 pub struct BodyValidator<T>(pub T);
 
 impl<S, T> FromRequest<S> for BodyValidator<T>
 where
-    T: Validate,
+    T: Validate + serde::de::DeserializeOwned,
     S: Send + Sync,
-    Json<T>: FromRequest<S>,
 {
     type Rejection = HttpResponse;
 
     async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
-        let Json(data) =
-            Json::<T>::from_request(req, state).await.map_err(|_| {
-                HttpResponse::build()
-                    .code(400)
-                    .body(json!({ "error": "Invalid request body" }))
-            })?;
-
-        data.validate().map_err(|e| {
-            HttpResponse::build().code(400).body(json!({
-                "message": "Invalid request body",
-                "errors": e.to_string()
-            }))
-        })?;
+        let Json(data) = Json::<T>::from_request(req, state)
+            .await
+            .map_err(json_rejection_problem)?;
+
+        data.validate()
+            .map_err(|e| validation_problem("request.invalid_body", &e))?;
 
         Ok(BodyValidator(data))
     }
@@ -45,29 +87,91 @@ impl<T> Deref for QueryValidator<T> {
     }
 }
 
-#[allow(dead_code)]
-impl<S, T> FromRequest<S> for QueryValidator<T>
+// Only reads the request parts, so it can be combined with `BodyValidator`
+
+impl<S, T> FromRequestParts<S> for QueryValidator<T>
 where
     S: Send + Sync,
     T: Validate + for<'de> serde::Deserialize<'de> + Send,
 {
     type Rejection = HttpResponse;
 
-    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
-        let Query(value) =
-            Query::<T>::from_request(req, state).await.map_err(|_| {
-                HttpResponse::build()
-                    .code(400)
-                    .body(json!({ "error": "Invalid Query format" }))
-            })?;
-
-        value.validate().map_err(|err| {
-            HttpResponse::build().code(400).body(json!({
-                "error": "Invalid Query format",
-                "errors": err.to_string()
-            }))
-        })?;
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &S,
+    ) -> Result<Self, Self::Rejection> {
+        let Query(value) = Query::<T>::from_request_parts(parts, state)
+            .await
+            .map_err(query_rejection_problem)?;
+
+        value
+            .validate()
+            .map_err(|e| validation_problem("request.invalid_query", &e))?;
 
         Ok(Self(value))
     }
 }
+
+// The authenticated user of the request, decoded from the access token
+// sent in the `Authorization: Bearer <token>` header.
+
+// When the route is protected by the `require_auth` layer the user was
+// already decoded and stored in the request extensions, so it is reused.
+
+#[derive(Debug, Clone)]
+pub struct AuthUser {
+    pub id: Uuid,
+    pub permissions: Vec<String>,
+}
+
+impl AuthUser {
+    pub fn has_permission(&self, permission: &str) -> bool {
+        self.permissions.iter().any(|p| p == permission)
+    }
+
+    // Whether the user can act on the account identified by `user_id`,
+    // either because it is its own account or because it was granted
+    // the given permission (e.g. admins).
+
+    pub fn can_manage(&self, user_id: &str, permission: &str) -> bool {
+        self.has_permission(permission)
+            || Uuid::parse_str(user_id).is_ok_and(|id| id == self.id)
+    }
+}
+
+impl<S> FromRequestParts<S> for AuthUser
+where
+    S: Send + Sync,
+    Arc<AppModule>: FromRef<S>,
+{
+    type Rejection = HttpResponse;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &S,
+    ) -> Result<Self, Self::Rejection> {
+        if let Some(user) = parts.extensions.get::<AuthUser>() {
+            return Ok(user.clone());
+        }
+
+        let token = parts
+            .headers
+            .get(AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .ok_or(AuthError::MissingToken)?;
+
+        let module = Arc::<AppModule>::from_ref(state);
+        let tokens: &dyn TokenService = module.resolve_ref();
+        let claims = tokens.verify(TokenKind::Access, token.trim())?;
+
+        let user = AuthUser {
+            id: claims.sub,
+            permissions: claims.permissions,
+        };
+
+        parts.extensions.insert(user.clone());
+
+        Ok(user)
+    }
+}
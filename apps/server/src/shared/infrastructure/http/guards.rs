@@ -0,0 +1,52 @@
+use std::{future::Future, pin::Pin};
+
+use axum::{
+    extract::Request,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use axum_responses::http::HttpResponse;
+
+use super::extractors::AuthUser;
+use crate::features::auth::domain::AuthError;
+
+type GuardFuture = Pin<Box<dyn Future<Output = Response> + Send>>;
+
+// Middleware that rejects every request without a valid access token.
+// Meant to be applied to whole routers with `route_layer`, e.g:
+
+// Router::new()
+//     .route("/users/{id}", patch(update_user))
+//     .route_layer(from_fn_with_state(state.clone(), require_auth))
+
+// The `AuthUser` extractor stores the decoded user in the request
+// extensions, so handlers behind this layer can extract it for free.
+
+pub async fn require_auth(_: AuthUser, request: Request, next: Next) -> Response {
+    next.run(request).await
+}
+
+// Declarative permission check, builds a middleware that only lets
+// through authenticated users holding `permission`, e.g:
+
+// Router::new()
+//     .route("/roles", get(get_roles))
+//     .route_layer(from_fn_with_state(
+//         state.clone(),
+//         require_permission(ROLES_MANAGE),
+//     ))
+
+pub fn require_permission(
+    permission: &'static str,
+) -> impl Fn(AuthUser, Request, Next) -> GuardFuture + Clone + Send + Sync + 'static
+{
+    move |user: AuthUser, request: Request, next: Next| {
+        Box::pin(async move {
+            if !user.has_permission(permission) {
+                return HttpResponse::from(AuthError::Forbidden).into_response();
+            }
+
+            next.run(request).await
+        })
+    }
+}
@@ -1,9 +1,23 @@
 use std::time::Duration;
 
-use axum::http;
+use axum::{
+    extract::{MatchedPath, Request},
+    http,
+    middleware::Next,
+    response::Response,
+};
+use opentelemetry_sdk::trace::SdkTracerProvider;
 use tower_http::classify::{ServerErrorsAsFailures, SharedClassifier};
 use tower_http::trace::{MakeSpan, OnRequest, OnResponse, TraceLayer};
-use tracing::Span;
+use tracing::{field, Span};
+
+use crate::shared::{
+    infrastructure::{
+        extract_trace_context, init_tracing, inject_trace_context,
+        request_id::REQUEST_ID_HEADER,
+    },
+    settings::Settings,
+};
 
 #[derive(Clone, Debug)]
 pub struct HttpLogger {
@@ -13,20 +27,20 @@ pub struct HttpLogger {
         TraceOnRequest,
         TraceOnResponse,
     >,
+    // Flushes the pending spans, it must be shut down before exiting
+    pub provider: SdkTracerProvider,
 }
 
 impl HttpLogger {
-    pub fn new() -> Self {
-        tracing_subscriber::fmt()
-            .with_target(false)
-            .compact()
-            .init();
+    pub fn new(settings: &Settings) -> Self {
+        let provider = init_tracing(&settings.logging, &settings.telemetry);
 
         HttpLogger {
             layer: TraceLayer::new_for_http()
                 .make_span_with(TraceMakeSpan)
                 .on_request(TraceOnRequest)
                 .on_response(TraceOnResponse),
+            provider,
         }
     }
 }
@@ -38,8 +52,31 @@ impl<B> MakeSpan<B> for TraceMakeSpan {
     fn make_span(&mut self, request: &http::Request<B>) -> Span {
         let method = request.method().as_str();
         let path = request.uri().path();
+        let route = request
+            .extensions()
+            .get::<MatchedPath>()
+            .map_or(path, MatchedPath::as_str);
+        let request_id = request
+            .headers()
+            .get(&REQUEST_ID_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default();
+
+        let span = tracing::info_span!(
+            "request",
+            %method,
+            %path,
+            %request_id,
+            otel.name = format!("{method} {route}"),
+            otel.kind = "server",
+            otel.status_code = field::Empty,
+            http.route = route,
+            http.response.status_code = field::Empty,
+            use_case = field::Empty,
+        );
 
-        tracing::info_span!("request", %method, %path)
+        extract_trace_context(request.headers(), &span);
+        span
     }
 }
 
@@ -56,12 +93,28 @@ impl<B> OnRequest<B> for TraceOnRequest {
 pub struct TraceOnResponse;
 
 impl<B> OnResponse<B> for TraceOnResponse {
-    fn on_response(self, response: &http::Response<B>, latency: Duration, _: &Span) {
-        tracing::info!(
-            "HTTP - [{}] - [{}ms]",
-            response.status().as_u16(),
-            latency.as_millis()
-        );
-        println!("");
+    fn on_response(
+        self,
+        response: &http::Response<B>,
+        latency: Duration,
+        span: &Span,
+    ) {
+        let status = response.status();
+
+        span.record("http.response.status_code", status.as_u16());
+        if status.is_server_error() {
+            span.record("otel.status_code", "ERROR");
+        }
+
+        tracing::info!("HTTP - [{}] - [{}ms]", status.as_u16(), latency.as_millis());
     }
 }
+
+// Returns the `traceparent` of the request span, it must run inside the
+// `HttpLogger` layer.
+
+pub async fn propagate_trace_context(request: Request, next: Next) -> Response {
+    let mut response = next.run(request).await;
+    inject_trace_context(response.headers_mut());
+    response
+}
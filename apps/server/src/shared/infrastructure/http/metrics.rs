@@ -0,0 +1,260 @@
+use std::{
+    any::type_name,
+    future::Future,
+    sync::OnceLock,
+    time::{Duration, Instant},
+};
+
+use axum::{
+    extract::{MatchedPath, Request, State},
+    middleware::Next,
+    response::Response,
+    routing::get,
+    Router,
+};
+use metrics_exporter_prometheus::{Matcher, PrometheusBuilder, PrometheusHandle};
+use shaku::HasComponent;
+use tracing::{Instrument, Span};
+use utoipa::OpenApi;
+
+use crate::shared::infrastructure::{AppState, DatabaseConnection};
+
+// |--------------------------------------------------------------------|
+// |             Metric               |  Type      |       Labels       |
+// |--------------------------------------------------------------------|
+// |  http_requests_total             |  counter   | method,route,status|
+// |  http_request_duration_seconds   |  histogram | method,route,status|
+// |  use_case_duration_seconds       |  histogram | use_case,outcome   |
+// |  db_pool_connections             |  gauge     | state              |
+// |  db_pool_max_connections         |  gauge     |                    |
+// |  db_pool_acquire_duration_seconds|  histogram |                    |
+// |--------------------------------------------------------------------|
+
+const LATENCY_BUCKETS: &[f64] = &[
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+// The recorder is global to the process, it is installed only once even
+// if the application (e.g. in tests) builds several routers.
+
+fn prometheus_handle() -> PrometheusHandle {
+    static HANDLE: OnceLock<PrometheusHandle> = OnceLock::new();
+
+    HANDLE
+        .get_or_init(|| {
+            PrometheusBuilder::new()
+                .set_buckets_for_metric(
+                    Matcher::Suffix("_duration_seconds".to_string()),
+                    LATENCY_BUCKETS,
+                )
+                .expect("Invalid histogram buckets")
+                .install_recorder()
+                .expect("Failed to install the metrics recorder")
+        })
+        .clone()
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(render_metrics),
+    tags((name = "metrics", description = "Scraped by Prometheus"))
+)]
+pub struct MetricsApi;
+
+pub fn router(state: AppState) -> Router {
+    prometheus_handle();
+
+    Router::new()
+        .route("/metrics", get(render_metrics))
+        .with_state(state)
+}
+
+// Requests that don't match any route are grouped under `unmatched`, so
+// random paths can't create an unbounded number of series.
+
+pub async fn track_requests(request: Request, next: Next) -> Response {
+    let start = Instant::now();
+    let method = request.method().to_string();
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| "unmatched".to_string());
+
+    let response = next.run(request).await;
+
+    let labels = [
+        ("method", method),
+        ("route", route),
+        ("status", response.status().as_u16().to_string()),
+    ];
+
+    metrics::counter!("http_requests_total", &labels).increment(1);
+    metrics::histogram!("http_request_duration_seconds", &labels)
+        .record(start.elapsed().as_secs_f64());
+
+    response
+}
+
+// Use cases are named after their trait, e.g. `GetUsersCase` for
+// `dyn server::features::user::application::interfaces::GetUsersCase`
+
+pub fn use_case_name<T: ?Sized>() -> &'static str {
+    let name = type_name::<T>();
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+// Runs a use case inside its own span and records how long its `execute`
+// took, failed when it returned an error. Handlers go through
+// `UseCase::run`, jobs call it directly, e.g:
+
+// observe(use_case, |use_case| use_case.execute()).await
+
+// The name is also added to the request span, if there is one.
+
+pub async fn observe<'a, T, F, O, E>(
+    use_case: &'a T,
+    execute: impl FnOnce(&'a T) -> F,
+) -> Result<O, E>
+where
+    T: ?Sized,
+    F: Future<Output = Result<O, E>>,
+{
+    let name = use_case_name::<T>();
+    Span::current().record("use_case", name);
+
+    let span = tracing::info_span!(
+        "use_case",
+        otel.name = name,
+        otel.status_code = tracing::field::Empty,
+    );
+
+    let start = Instant::now();
+    let result = execute(use_case).instrument(span.clone()).await;
+
+    if result.is_err() {
+        span.record("otel.status_code", "ERROR");
+    }
+
+    record_use_case(name, result.is_err(), start.elapsed());
+    result
+}
+
+fn record_use_case(use_case: &'static str, failed: bool, elapsed: Duration) {
+    let outcome = if failed { "error" } else { "ok" };
+
+    metrics::histogram!(
+        "use_case_duration_seconds",
+        "use_case" => use_case,
+        "outcome" => outcome,
+    )
+    .record(elapsed.as_secs_f64());
+}
+
+// The pool gauges are sampled on every scrape from the counters of the
+// pool, the scrape never acquires a connection itself. The wait for a
+// connection is recorded on every acquire (see `database::acquire`).
+
+#[utoipa::path(
+    get,
+    path = "/metrics",
+    tag = "metrics",
+    responses((
+        status = 200,
+        description = "Prometheus text exposition format",
+        body = String,
+        content_type = "text/plain"
+    ))
+)]
+async fn render_metrics(State(state): State<AppState>) -> String {
+    let database: &dyn DatabaseConnection = state.module.resolve_ref();
+    let pool = database.get_pool();
+
+    let size = pool.size();
+    let idle = pool.num_idle() as u32;
+
+    metrics::gauge!("db_pool_connections", "state" => "idle").set(idle);
+    metrics::gauge!("db_pool_connections", "state" => "in_use")
+        .set(size.saturating_sub(idle));
+    metrics::gauge!("db_pool_max_connections")
+        .set(pool.options().get_max_connections());
+
+    prometheus_handle().render()
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{
+        body::{to_bytes, Body},
+        http::{Method, Request},
+    };
+    use serde_json::json;
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::features::user::application::interfaces::GetUsersCase;
+
+    struct PassingCase;
+    struct FailingCase;
+    use crate::shared::infrastructure::testing::{json_request, TestModuleBuilder};
+
+    #[test]
+    fn names_the_use_cases_after_their_trait() {
+        assert_eq!(use_case_name::<dyn GetUsersCase>(), "GetUsersCase");
+    }
+
+    #[tokio::test]
+    async fn records_every_use_case_with_the_outcome_of_its_result() {
+        let handle = prometheus_handle();
+
+        let passed = observe(&PassingCase, |_| async { Ok::<_, ()>(()) }).await;
+        let failed = observe(&FailingCase, |_| async { Err::<(), _>(()) }).await;
+
+        assert!(passed.is_ok() && failed.is_err());
+
+        let metrics = handle.render();
+        let series = "use_case_duration_seconds_count";
+        assert!(metrics.contains(&format!(
+            r#"{series}{{use_case="PassingCase",outcome="ok"}} 1"#
+        )));
+        assert!(metrics.contains(&format!(
+            r#"{series}{{use_case="FailingCase",outcome="error"}} 1"#
+        )));
+    }
+
+    // The recorder is shared by every test, so the use case that must not
+    // be recorded is one no other test sends requests to
+
+    #[tokio::test]
+    async fn records_the_use_cases_that_ran() {
+        let app = TestModuleBuilder::new().build();
+
+        app.send(json_request(
+            Method::POST,
+            "/users/verify/resend",
+            json!({ "email": "alice@example.com" }),
+        ))
+        .await;
+        app.send(json_request(
+            Method::POST,
+            "/auth/password/reset",
+            json!({ "token": "" }),
+        ))
+        .await;
+
+        let response = app
+            .router()
+            .oneshot(Request::get("/metrics").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let metrics = String::from_utf8(bytes.to_vec()).unwrap();
+
+        let series = "use_case_duration_seconds_count";
+        assert!(metrics.contains(&format!(
+            r#"{series}{{use_case="ResendVerificationCase",outcome="ok"}}"#
+        )));
+        // Rejected before the use case was called
+        assert!(!metrics.contains(r#"use_case="ResetPasswordCase""#));
+    }
+}
@@ -0,0 +1,246 @@
+use std::collections::BTreeMap;
+
+use axum::{http::StatusCode, Router};
+use serde_json::Value;
+use utoipa::{
+    openapi::{
+        example::ExampleBuilder,
+        path::{HttpMethod, Operation},
+        security::{Http, HttpAuthScheme, SecurityScheme},
+        ContentBuilder, OpenApi as Spec, Ref, ResponseBuilder,
+    },
+    Modify, OpenApi, ToSchema,
+};
+use utoipa_swagger_ui::SwaggerUi;
+
+use super::problem::{Problem, PROBLEM_CONTENT_TYPE};
+use crate::features::{
+    auth::infrastructure::AuthApi, role::infrastructure::RoleApi,
+    user::infrastructure::UserApi,
+};
+use crate::shared::infrastructure::{metrics::MetricsApi, probes::HealthApi};
+
+// OpenAPI 3 specification of every route, generated from the handlers
+// annotated with `#[utoipa::path]`, the DTOs (`ToSchema`/`IntoParams`)
+// and the error enums of each feature (see `document_problems`).
+
+// |-----------------------------------------------------------------|
+// |      Route       |                    Content                   |
+// |-----------------------------------------------------------------|
+// |  /openapi.json   |  The specification                           |
+// |  /docs           |  Swagger UI, embedded in the binary          |
+// |-----------------------------------------------------------------|
+
+// The specification is also committed as `openapi.json`, a test fails
+// when it no longer matches the code. Regenerate it with:
+
+// UPDATE_OPENAPI=1 cargo test openapi
+
+pub const BEARER_AUTH: &str = "bearerAuth";
+
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "Server API",
+        description = "Errors are `application/problem+json` documents (RFC 7807), \
+                       match on their `code`. Send `Accept-Language` (en, es) to \
+                       get their messages translated."
+    ),
+    components(schemas(ProblemDocument, FieldError, MessageResponse)),
+    modifiers(&BearerAuth)
+)]
+struct ApiDoc;
+
+pub fn openapi() -> Spec {
+    let mut spec = ApiDoc::openapi();
+
+    spec.merge(AuthApi::openapi());
+    spec.merge(RoleApi::openapi());
+    spec.merge(UserApi::openapi());
+    spec.merge(HealthApi::openapi());
+    spec.merge(MetricsApi::openapi());
+
+    spec
+}
+
+pub fn router() -> Router {
+    Router::new().merge(SwaggerUi::new("/docs").url("/openapi.json", openapi()))
+}
+
+struct BearerAuth;
+
+impl Modify for BearerAuth {
+    fn modify(&self, spec: &mut Spec) {
+        let components = spec.components.get_or_insert_with(Default::default);
+        let mut scheme = Http::new(HttpAuthScheme::Bearer);
+        scheme.bearer_format = Some("JWT".to_string());
+
+        components.add_security_scheme(BEARER_AUTH, SecurityScheme::Http(scheme));
+    }
+}
+
+// Adds the error responses of a route, one per status listing the codes
+// it can answer with. Each code gets an example with its document, so
+// the statuses and codes come from the same `Problem` the API returns.
+
+pub fn document_problems(
+    spec: &mut Spec,
+    path: &str,
+    method: HttpMethod,
+    problems: impl IntoIterator<Item = Problem>,
+) {
+    let operation = spec
+        .paths
+        .paths
+        .get_mut(path)
+        .and_then(|item| match method {
+            HttpMethod::Get => item.get.as_mut(),
+            HttpMethod::Post => item.post.as_mut(),
+            HttpMethod::Put => item.put.as_mut(),
+            HttpMethod::Patch => item.patch.as_mut(),
+            HttpMethod::Delete => item.delete.as_mut(),
+            _ => None,
+        })
+        .unwrap_or_else(|| {
+            panic!("No documented operation of {path} for the method")
+        });
+
+    let mut by_status: BTreeMap<StatusCode, Vec<Problem>> = BTreeMap::new();
+
+    for problem in problems {
+        by_status.entry(problem.status()).or_default().push(problem);
+    }
+
+    for (status, problems) in by_status {
+        add_problem_response(operation, status, problems);
+    }
+}
+
+fn add_problem_response(
+    operation: &mut Operation,
+    status: StatusCode,
+    problems: Vec<Problem>,
+) {
+    let codes: Vec<String> = problems
+        .iter()
+        .map(|problem| format!("`{}`", problem.code()))
+        .collect();
+
+    let examples = problems.into_iter().map(|problem| {
+        let code = problem.code();
+        let example = ExampleBuilder::new()
+            .value(Some(Value::Object(problem.into_document())))
+            .build();
+
+        (code, example)
+    });
+
+    let content = ContentBuilder::new()
+        .schema(Some(Ref::from_schema_name("Problem")))
+        .examples_from_iter(examples)
+        .build();
+
+    let response = ResponseBuilder::new()
+        .description(codes.join(", "))
+        .content(PROBLEM_CONTENT_TYPE, content)
+        .build();
+
+    operation
+        .responses
+        .responses
+        .insert(status.as_u16().to_string(), response.into());
+}
+
+// Problems of the extractors, for the routes that read a JSON body or
+// query parameters (see `validation`)
+
+pub fn body_problems() -> Vec<Problem> {
+    vec![
+        Problem::new(StatusCode::BAD_REQUEST, "request.invalid_body"),
+        Problem::new(StatusCode::BAD_REQUEST, "request.malformed_json"),
+        Problem::new(StatusCode::UNPROCESSABLE_ENTITY, "request.missing_field"),
+        Problem::new(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "request.invalid_field_type",
+        ),
+        Problem::new(
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            "request.unsupported_media_type",
+        ),
+    ]
+}
+
+pub fn query_problems() -> Vec<Problem> {
+    vec![
+        Problem::new(StatusCode::BAD_REQUEST, "request.invalid_query"),
+        Problem::new(StatusCode::BAD_REQUEST, "request.invalid_field_type"),
+    ]
+}
+
+// Schemas of the documents built at runtime with `json!`, they only
+// exist for the specification.
+
+#[allow(dead_code)]
+#[derive(ToSchema)]
+#[schema(as = Problem, rename_all = "camelCase")]
+struct ProblemDocument {
+    /// `/problems/<code>`
+    r#type: String,
+    /// Reason phrase of the status
+    title: String,
+    status: u16,
+    /// Message of the code, in the negotiated language
+    detail: String,
+    /// Path of the request
+    instance: String,
+    /// Stable identifier clients should match on, e.g. `user.not_found`
+    code: String,
+    /// `X-Request-Id` of the request
+    request_id: String,
+    /// Input the problem refers to, e.g. `email`
+    field: Option<String>,
+    /// Errors of each invalid field, keyed by its name in the request
+    errors: Option<BTreeMap<String, Vec<FieldError>>>,
+}
+
+#[allow(dead_code)]
+#[derive(ToSchema)]
+struct FieldError {
+    /// e.g. `length`, `email`, `passwords_mismatch`
+    code: String,
+    message: String,
+    /// Bounds of the rule, e.g. `{ "min": 5, "max": 50 }`
+    params: BTreeMap<String, Value>,
+}
+
+#[allow(dead_code)]
+#[derive(ToSchema)]
+pub struct MessageResponse {
+    message: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs};
+
+    use super::*;
+
+    #[test]
+    fn committed_openapi_spec_is_up_to_date() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/openapi.json");
+        let spec = openapi().to_pretty_json().unwrap() + "\n";
+
+        if env::var_os("UPDATE_OPENAPI").is_some() {
+            fs::write(path, &spec).unwrap();
+            return;
+        }
+
+        let committed = fs::read_to_string(path).unwrap_or_default();
+
+        assert!(
+            committed == spec,
+            "openapi.json is out of date, regenerate it with \
+             `UPDATE_OPENAPI=1 cargo test openapi` and commit it"
+        );
+    }
+}
@@ -0,0 +1,152 @@
+use std::time::Duration;
+
+use axum::{extract::State, http::StatusCode, routing::get, Router};
+use axum_responses::http::HttpResponse;
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use shaku::HasComponent;
+use utoipa::{OpenApi, ToSchema};
+
+use crate::shared::{
+    application::{CheckResult, HealthChecks},
+    infrastructure::AppState,
+};
+
+// |-----------------------------------------------------------------|
+// |     Route      |                   Meaning                      |
+// |-----------------------------------------------------------------|
+// |  /health/live  |  The process is up, restart it when it fails   |
+// |  /health/ready |  Every dependency is up, route traffic to it   |
+// |  /health       |  Alias of /health/live                         |
+// |-----------------------------------------------------------------|
+
+// A check that takes longer than this is reported as down
+const CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+
+pub fn router(state: AppState) -> Router {
+    Router::new()
+        .route("/health", get(health))
+        .route("/health/live", get(live))
+        .route("/health/ready", get(ready))
+        .with_state(state)
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(live, ready, health),
+    components(schemas(Liveness, Readiness, DatabaseCheck)),
+    tags((name = "health", description = "Probes for the orchestrator"))
+)]
+pub struct HealthApi;
+
+#[utoipa::path(
+    get,
+    path = "/health/live",
+    tag = "health",
+    responses((status = 200, description = "The process is up", body = Liveness))
+)]
+pub async fn live() -> HttpResponse {
+    HttpResponse::build().status(StatusCode::OK).body(json!({
+        "status": "up",
+        "time": chrono::Utc::now().to_rfc3339(),
+    }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/health",
+    tag = "health",
+    responses((status = 200, description = "Same as `/health/live`", body = Liveness))
+)]
+pub async fn health() -> HttpResponse {
+    live().await
+}
+
+#[utoipa::path(
+    get,
+    path = "/health/ready",
+    tag = "health",
+    responses(
+        (status = 200, description = "Every check is up", body = Readiness),
+        (status = 503, description = "At least one check is down", body = Readiness)
+    )
+)]
+pub async fn ready(State(state): State<AppState>) -> HttpResponse {
+    let registry: &dyn HealthChecks = state.module.resolve_ref();
+
+    let mut healthy = true;
+    let mut checks = Map::new();
+
+    for check in registry.all() {
+        let result = tokio::time::timeout(CHECK_TIMEOUT, check.check())
+            .await
+            .unwrap_or_else(|_| CheckResult::down("Check timed out"));
+
+        healthy &= result.healthy;
+
+        let mut report = result.details;
+        report.insert("status".to_string(), json!(status_of(result.healthy)));
+        checks.insert(check.name().to_string(), Value::Object(report));
+    }
+
+    let status = match healthy {
+        true => StatusCode::OK,
+        false => StatusCode::SERVICE_UNAVAILABLE,
+    };
+
+    HttpResponse::build().status(status).body(json!({
+        "status": status_of(healthy),
+        "checks": checks,
+    }))
+}
+
+fn status_of(healthy: bool) -> &'static str {
+    match healthy {
+        true => "up",
+        false => "down",
+    }
+}
+
+// Schemas of the probe responses, only used by the specification
+
+#[allow(dead_code)]
+#[derive(ToSchema)]
+struct Liveness {
+    #[schema(example = "up")]
+    status: String,
+    time: chrono::DateTime<chrono::Utc>,
+}
+
+#[allow(dead_code)]
+#[derive(ToSchema)]
+struct Readiness {
+    /// `up` or `down`
+    status: String,
+    /// Result of each check by name
+    checks: ReadinessChecks,
+}
+
+#[allow(dead_code)]
+#[derive(ToSchema)]
+struct ReadinessChecks {
+    database: DatabaseCheck,
+}
+
+// Only `status` and `error` are sent when the database is down
+
+#[allow(dead_code)]
+#[derive(Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+struct DatabaseCheck {
+    /// `up` or `down`
+    status: String,
+    /// Why the check is down
+    error: Option<String>,
+    /// Duration of the `SELECT 1` ping in milliseconds
+    #[schema(example = 1.2)]
+    latency_ms: Option<f64>,
+    /// Latest `applied` migration and the `expected` one
+    migrations: Option<Value>,
+    /// `size`, `idle`, `inUse`, `max` connections and the `saturation`
+    pool: Option<Value>,
+}
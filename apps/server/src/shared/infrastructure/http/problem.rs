@@ -0,0 +1,255 @@
+use axum::{
+    body::{to_bytes, Body, HttpBody},
+    extract::Request,
+    http::{header, HeaderValue, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use axum_responses::http::HttpResponse;
+use serde_json::{Map, Value};
+
+use super::{request_id::REQUEST_ID_HEADER, validation::validation_key};
+use crate::shared::infrastructure::i18n::{self, Locale};
+
+// Shared error model of the API, every error response is an RFC 7807
+// `application/problem+json` document:
+
+// {
+//     "type": "/problems/user.not_found",
+//     "title": "Not Found",
+//     "status": 404,
+//     "detail": "User not found",
+//     "instance": "/users/0196...",
+//     "code": "user.not_found",
+//     "requestId": "4bf92f35-..."
+// }
+
+// Features build one from their error enums (see `errors.rs`), `code`
+// is the stable, machine-readable identifier clients should match on and
+// the key of its message in the catalogs (see `i18n`). `instance`,
+// `requestId` and the language of the messages are only known at the
+// HTTP layer, they are resolved by the `problem_details` middleware.
+
+pub const PROBLEM_CONTENT_TYPE: &str = "application/problem+json";
+
+const MAX_PROBLEM_SIZE: usize = 64 * 1024;
+
+#[derive(Debug, Clone)]
+pub struct Problem {
+    status: StatusCode,
+    code: &'static str,
+    detail: String,
+    extensions: Map<String, Value>,
+}
+
+impl Problem {
+    pub fn new(status: StatusCode, code: &'static str) -> Self {
+        let detail = i18n::message(Locale::DEFAULT, code, &Map::new())
+            .unwrap_or_else(|| status.canonical_reason().unwrap_or("Error").into());
+
+        Self {
+            status,
+            code,
+            detail,
+            extensions: Map::new(),
+        }
+    }
+
+    // Detail built at runtime, e.g. the error of the JSON parser. These
+    // codes have no entry in the catalogs, so they are not translated.
+
+    pub fn detail(mut self, detail: impl Into<String>) -> Self {
+        self.detail = detail.into();
+        self
+    }
+
+    // Name of the input the problem refers to, e.g. `email`
+
+    pub fn field(self, field: &str) -> Self {
+        self.with("field", Value::from(field))
+    }
+
+    // Extension members, added next to the standard ones
+
+    pub fn with(mut self, key: &str, value: Value) -> Self {
+        self.extensions.insert(key.to_string(), value);
+        self
+    }
+
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    pub fn code(&self) -> &'static str {
+        self.code
+    }
+
+    pub fn into_document(self) -> Map<String, Value> {
+        let mut document = Map::new();
+
+        document.insert("type".into(), format!("/problems/{}", self.code).into());
+        document.insert(
+            "title".into(),
+            self.status.canonical_reason().unwrap_or("Error").into(),
+        );
+        document.insert("status".into(), self.status.as_u16().into());
+        document.insert("detail".into(), self.detail.into());
+        document.insert("code".into(), self.code.into());
+        document.extend(self.extensions);
+        document
+    }
+}
+
+impl From<Problem> for HttpResponse {
+    fn from(problem: Problem) -> Self {
+        HttpResponse::build()
+            .status(problem.status)
+            .add_header(header::CONTENT_TYPE.as_str(), PROBLEM_CONTENT_TYPE)
+            .body(Value::Object(problem.into_document()))
+    }
+}
+
+impl IntoResponse for Problem {
+    fn into_response(self) -> Response {
+        HttpResponse::from(self).into_response()
+    }
+}
+
+// Completes the problems returned by the handlers with the request path
+// and id, translates their messages to the language negotiated with
+// `Accept-Language`, and turns the plain text errors produced by axum
+// itself (e.g. unknown routes, methods or malformed path parameters)
+// into problems.
+
+pub async fn problem_details(request: Request, next: Next) -> Response {
+    let instance = request.uri().path().to_string();
+    let locale = Locale::negotiate(
+        request
+            .headers()
+            .get(header::ACCEPT_LANGUAGE)
+            .and_then(|value| value.to_str().ok()),
+    );
+    let request_id = request
+        .headers()
+        .get(&REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(String::from);
+
+    let response = next.run(request).await;
+    let status = response.status();
+
+    if !(status.is_client_error() || status.is_server_error()) {
+        return response;
+    }
+
+    let content_type = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default();
+
+    let is_problem = content_type.starts_with(PROBLEM_CONTENT_TYPE);
+    let is_json = content_type.contains("json");
+
+    // Streamed or unusually large bodies are left untouched
+    let is_small = response
+        .body()
+        .size_hint()
+        .exact()
+        .is_some_and(|size| size as usize <= MAX_PROBLEM_SIZE);
+
+    if (is_json && !is_problem) || !is_small {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let bytes = to_bytes(body, MAX_PROBLEM_SIZE).await.unwrap_or_default();
+
+    let mut problem = match serde_json::from_slice::<Value>(&bytes) {
+        Ok(Value::Object(problem)) if is_problem => problem,
+        _ => {
+            let problem = Problem::new(status, status_code(status));
+            let text = String::from_utf8_lossy(&bytes);
+
+            match text.trim() {
+                "" => problem.into_document(),
+                text => problem.detail(text).into_document(),
+            }
+        }
+    };
+
+    localize(&mut problem, locale);
+
+    problem
+        .entry("instance")
+        .or_insert_with(|| Value::from(instance));
+
+    if let Some(id) = request_id {
+        problem.insert("requestId".into(), Value::from(id));
+    }
+
+    parts.headers.remove(header::CONTENT_LENGTH);
+    parts.headers.insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static(PROBLEM_CONTENT_TYPE),
+    );
+    parts.headers.insert(
+        header::CONTENT_LANGUAGE,
+        HeaderValue::from_static(locale.tag()),
+    );
+
+    Response::from_parts(parts, Body::from(Value::Object(problem).to_string()))
+}
+
+// Replaces the detail and the message of each field error with the ones
+// of the catalog, messages without an entry are kept as they are.
+
+fn localize(problem: &mut Map<String, Value>, locale: Locale) {
+    let no_params = Map::new();
+
+    if let Some(Value::String(code)) = problem.get("code") {
+        if let Some(detail) = i18n::message(locale, code, &no_params) {
+            problem.insert("detail".into(), detail.into());
+        }
+    }
+
+    let Some(Value::Object(fields)) = problem.get_mut("errors") else {
+        return;
+    };
+
+    for entry in fields
+        .values_mut()
+        .filter_map(Value::as_array_mut)
+        .flatten()
+    {
+        let Some(code) = entry.get("code").and_then(Value::as_str) else {
+            continue;
+        };
+
+        let params = match entry.get("params") {
+            Some(Value::Object(params)) => params,
+            _ => &no_params,
+        };
+
+        if let Some(message) =
+            i18n::message(locale, &validation_key(code, params), params)
+        {
+            entry["message"] = message.into();
+        }
+    }
+}
+
+// Codes of the problems built from a bare status
+
+fn status_code(status: StatusCode) -> &'static str {
+    match status {
+        StatusCode::BAD_REQUEST => "request.invalid",
+        StatusCode::NOT_FOUND => "request.route_not_found",
+        StatusCode::METHOD_NOT_ALLOWED => "request.method_not_allowed",
+        StatusCode::PAYLOAD_TOO_LARGE => "request.payload_too_large",
+        StatusCode::UNSUPPORTED_MEDIA_TYPE => "request.unsupported_media_type",
+        StatusCode::UNPROCESSABLE_ENTITY => "request.unprocessable",
+        status if status.is_server_error() => "server.unexpected_error",
+        _ => "request.error",
+    }
+}
@@ -0,0 +1,45 @@
+use axum::{
+    extract::Request,
+    http::{HeaderName, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+use uuid::Uuid;
+
+pub const REQUEST_ID_HEADER: HeaderName = HeaderName::from_static("x-request-id");
+
+// Ids sent by a proxy or another service are kept so the logs of every
+// hop can be correlated, anything else gets a new UUID. It is written
+// back in the `X-Request-Id` response header, added as `requestId` to
+// error responses (see `problem_details`) and recorded in the request
+// span (see `HttpLogger`), so every log line of the request carries it.
+
+// Must be the outermost layer, the span reads the id from the headers.
+
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+pub async fn request_id(mut request: Request, next: Next) -> Response {
+    let id = request
+        .headers()
+        .get(&REQUEST_ID_HEADER)
+        .filter(|value| is_valid(value))
+        .cloned()
+        .unwrap_or_else(|| {
+            HeaderValue::from_str(&Uuid::new_v4().to_string())
+                .expect("A UUID is a valid header value")
+        });
+
+    request.headers_mut().insert(REQUEST_ID_HEADER, id.clone());
+
+    let mut response = next.run(request).await;
+    response.headers_mut().insert(REQUEST_ID_HEADER, id);
+    response
+}
+
+fn is_valid(value: &HeaderValue) -> bool {
+    let bytes = value.as_bytes();
+
+    !bytes.is_empty()
+        && bytes.len() <= MAX_REQUEST_ID_LEN
+        && bytes.iter().all(|byte| byte.is_ascii_graphic())
+}
@@ -0,0 +1,446 @@
+use std::{borrow::Cow, error::Error};
+
+use axum::{
+    extract::rejection::{JsonRejection, QueryRejection},
+    http::StatusCode,
+};
+use serde_json::{json, Map, Value};
+use validator::{ValidationError, ValidationErrors, ValidationErrorsKind};
+
+use super::problem::Problem;
+use crate::shared::infrastructure::i18n::{self, Locale};
+
+// Validation problems list every offending field under `errors`, keyed by
+// the name used in the request (camelCase) so clients can highlight it:
+
+// "errors": {
+//     "email": [{ "code": "email", "message": "...", "params": {} }],
+//     "confirmPassword": [{ "code": "passwords_mismatch", ... }]
+// }
+
+// Errors of schema validators are attached to the field named by their
+// `field` param (see `invalid_field`), the rest are listed under `_schema`.
+// Messages come from the catalogs, `validation.<code>` (see `i18n`).
+
+const SCHEMA_KEY: &str = "_schema";
+
+pub fn invalid(code: &'static str) -> ValidationError {
+    ValidationError::new(code)
+}
+
+// For schema validators, `field` is the name used in the request
+
+pub fn invalid_field(field: &'static str, code: &'static str) -> ValidationError {
+    let mut error = invalid(code);
+    error.add_param(Cow::Borrowed("field"), &field);
+    error
+}
+
+// Catalog key of a field error, `length` and `range` depend on the
+// bounds given, e.g. `validation.length.between`
+
+pub fn validation_key(code: &str, params: &Map<String, Value>) -> String {
+    let bounds = match (params.get("min"), params.get("max")) {
+        (Some(_), Some(_)) => "between",
+        (Some(_), None) => "min",
+        _ => "max",
+    };
+
+    match code {
+        "length" | "range" => format!("validation.{code}.{bounds}"),
+        code => format!("validation.{code}"),
+    }
+}
+
+pub fn validation_problem(code: &'static str, errors: &ValidationErrors) -> Problem {
+    let mut fields = Map::new();
+    collect_errors(errors, "", &mut fields);
+
+    Problem::new(StatusCode::BAD_REQUEST, code).with("errors", Value::Object(fields))
+}
+
+// Tells apart malformed JSON, a wrong content type and fields that are
+// missing or have the wrong type.
+
+pub fn json_rejection_problem(rejection: JsonRejection) -> Problem {
+    let status = rejection.status();
+
+    match rejection {
+        JsonRejection::MissingJsonContentType(_) => {
+            Problem::new(status, "request.unsupported_media_type")
+        }
+        JsonRejection::JsonSyntaxError(e) => {
+            Problem::new(status, "request.malformed_json").detail(
+                path_error::<serde_json::Error>(&e)
+                    .map_or_else(|| e.body_text(), |(_, message)| message),
+            )
+        }
+        JsonRejection::JsonDataError(e) => {
+            match path_error::<serde_json::Error>(&e) {
+                Some((path, message)) => field_problem(status, path, message),
+                None => Problem::new(status, "request.invalid_data")
+                    .detail(e.body_text()),
+            }
+        }
+        rejection => Problem::new(status, "request.unreadable_body")
+            .detail(rejection.body_text()),
+    }
+}
+
+pub fn query_rejection_problem(rejection: QueryRejection) -> Problem {
+    let status = rejection.status();
+
+    match path_error::<serde::de::value::Error>(&rejection) {
+        Some((path, message)) => field_problem(status, path, message),
+        None => Problem::new(status, "request.malformed_query")
+            .detail(rejection.body_text()),
+    }
+}
+
+// Serde quotes the submitted value in its errors, e.g. `invalid type:
+// string "x", expected u32`, only what was expected is sent back
+
+fn field_problem(status: StatusCode, path: String, message: String) -> Problem {
+    // Serde reports missing fields on the parent, e.g. "missing field `email`"
+    let missing = message
+        .strip_prefix("missing field `")
+        .and_then(|rest| rest.split('`').next());
+
+    let (code, field, entry) = match missing {
+        Some(name) => (
+            "request.missing_field",
+            join_path(&path, name),
+            json!({
+                "code": "required",
+                "message": validation_message("required", &Map::new()),
+                "params": {},
+            }),
+        ),
+        None => ("request.invalid_field_type", path, type_error(&message)),
+    };
+
+    Problem::new(status, code).with("errors", json!({ field: [entry] }))
+}
+
+fn type_error(message: &str) -> Value {
+    let mut params = Map::new();
+
+    // Errors of custom deserializers (e.g. a malformed date) don't say
+    let code = match message.split_once(", expected ") {
+        Some((_, expected)) => {
+            // The JSON errors end with the position, e.g. `at line 1 column 9`
+            let expected = expected.split(" at line ").next().unwrap_or(expected);
+            params.insert("expected".into(), expected.into());
+            "invalid_type"
+        }
+        None => "invalid",
+    };
+
+    json!({
+        "code": code,
+        "message": validation_message(code, &params),
+        "params": params,
+    })
+}
+
+// The rejections wrap the `serde_path_to_error` error that knows which
+// field failed, `E` is the error of the deserializer (json or query).
+
+fn path_error<E>(rejection: &(dyn Error + 'static)) -> Option<(String, String)>
+where
+    E: Error + 'static,
+{
+    let mut source = rejection.source();
+
+    while let Some(error) = source {
+        if let Some(e) = error.downcast_ref::<serde_path_to_error::Error<E>>() {
+            let path = match e.path().to_string() {
+                path if path == "." => String::new(),
+                path => path,
+            };
+
+            return Some((path, e.inner().to_string()));
+        }
+
+        source = error.source();
+    }
+
+    None
+}
+
+fn collect_errors(
+    errors: &ValidationErrors,
+    prefix: &str,
+    fields: &mut Map<String, Value>,
+) {
+    for (name, kind) in errors.errors() {
+        let path = join_path(prefix, &camel_case(name));
+
+        match kind {
+            ValidationErrorsKind::Field(list) => {
+                for error in list {
+                    let target = if name == "__all__" {
+                        match error.params.get("field").and_then(Value::as_str) {
+                            Some(field) => join_path(prefix, field),
+                            None => join_path(prefix, SCHEMA_KEY),
+                        }
+                    } else {
+                        path.clone()
+                    };
+
+                    let entries = fields
+                        .entry(target)
+                        .or_insert_with(|| Value::Array(Vec::new()));
+
+                    if let Value::Array(entries) = entries {
+                        entries.push(error_entry(error));
+                    }
+                }
+            }
+            ValidationErrorsKind::Struct(nested) => {
+                collect_errors(nested, &path, fields);
+            }
+            ValidationErrorsKind::List(items) => {
+                for (index, nested) in items {
+                    collect_errors(nested, &format!("{path}[{index}]"), fields);
+                }
+            }
+        }
+    }
+}
+
+// The submitted `value` is never echoed back, it may be a password
+
+fn error_entry(error: &ValidationError) -> Value {
+    let params: Map<String, Value> = error
+        .params
+        .iter()
+        .filter(|(key, _)| !matches!(key.as_ref(), "value" | "field"))
+        .map(|(key, value)| (key.to_string(), value.clone()))
+        .collect();
+
+    let message = match &error.message {
+        Some(message) => message.to_string(),
+        None => validation_message(&error.code, &params),
+    };
+
+    json!({ "code": error.code, "message": message, "params": params })
+}
+
+fn validation_message(code: &str, params: &Map<String, Value>) -> String {
+    i18n::message(Locale::DEFAULT, &validation_key(code, params), params)
+        .or_else(|| i18n::message(Locale::DEFAULT, "validation.invalid", params))
+        .unwrap_or_default()
+}
+
+// The DTOs use snake_case fields and expose them in camelCase
+
+fn camel_case(name: &str) -> String {
+    let mut words = name.split('_');
+    let mut camel = words.next().unwrap_or_default().to_string();
+
+    for word in words {
+        let mut chars = word.chars();
+        if let Some(first) = chars.next() {
+            camel.extend(first.to_uppercase());
+            camel.push_str(chars.as_str());
+        }
+    }
+
+    camel
+}
+
+fn join_path(prefix: &str, name: &str) -> String {
+    match prefix {
+        "" => name.to_string(),
+        prefix => format!("{prefix}.{name}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{
+        body::Body,
+        extract::{FromRequest, Json, Query, Request},
+        http::{header::CONTENT_TYPE, Uri},
+    };
+    use serde::Deserialize;
+    use validator::Validate;
+
+    use super::*;
+
+    #[allow(dead_code)]
+    #[derive(Debug, Deserialize, Validate)]
+    #[serde(rename_all = "camelCase")]
+    struct SignUp {
+        email: String,
+        #[validate(length(min = 8))]
+        confirm_password: String,
+        address: Option<Address>,
+    }
+
+    #[allow(dead_code)]
+    #[derive(Debug, Deserialize)]
+    struct Address {
+        zip: String,
+    }
+
+    #[allow(dead_code)]
+    #[derive(Debug, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Listing {
+        sort_by: SortBy,
+        per_page: Option<u32>,
+    }
+
+    #[derive(Debug, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    enum SortBy {
+        Email,
+    }
+
+    async fn json_rejection(content_type: &str, body: &str) -> JsonRejection {
+        let request = Request::builder()
+            .header(CONTENT_TYPE, content_type)
+            .body(Body::from(body.to_string()))
+            .unwrap();
+
+        Json::<SignUp>::from_request(request, &())
+            .await
+            .unwrap_err()
+    }
+
+    fn query_rejection(uri: &'static str) -> QueryRejection {
+        Query::<Listing>::try_from_uri(&Uri::from_static(uri)).unwrap_err()
+    }
+
+    fn field_errors(problem: Problem) -> Map<String, Value> {
+        match problem.into_document().remove("errors") {
+            Some(Value::Object(errors)) => errors,
+            errors => panic!("Expected field errors, got {errors:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn rejects_a_body_that_is_not_json() {
+        let problem = json_problem("text/plain", "{}").await;
+
+        assert_eq!(problem.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        assert_eq!(problem.code(), "request.unsupported_media_type");
+    }
+
+    #[tokio::test]
+    async fn rejects_malformed_json_with_the_parser_error() {
+        let problem = json_problem("application/json", r#"{"email": "#).await;
+
+        assert_eq!(problem.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(problem.code(), "request.malformed_json");
+        assert!(problem.into_document()["detail"]
+            .as_str()
+            .unwrap()
+            .contains("line 1"));
+    }
+
+    #[tokio::test]
+    async fn reports_a_missing_field_by_its_request_name() {
+        let body = r#"{"email": "alice@example.com"}"#;
+        let problem = json_problem("application/json", body).await;
+
+        assert_eq!(problem.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(problem.code(), "request.missing_field");
+        assert_eq!(
+            field_errors(problem)["confirmPassword"][0]["code"],
+            "required"
+        );
+    }
+
+    #[tokio::test]
+    async fn reports_a_field_of_the_wrong_type_by_its_request_name() {
+        let body = r#"{"email": "alice@example.com", "confirmPassword": 1}"#;
+        let problem = json_problem("application/json", body).await;
+
+        assert_eq!(problem.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(problem.code(), "request.invalid_field_type");
+
+        let errors = field_errors(problem);
+        assert_eq!(errors["confirmPassword"][0]["code"], "invalid_type");
+        assert_eq!(
+            errors["confirmPassword"][0]["params"]["expected"],
+            "a string"
+        );
+    }
+
+    #[tokio::test]
+    async fn finds_the_path_of_nested_fields() {
+        let body = r#"{
+            "email": "alice@example.com",
+            "confirmPassword": "Secret1!",
+            "address": { "zip": 1 }
+        }"#;
+        let rejection = json_rejection("application/json", body).await;
+        let missing = json_rejection("application/json", "{}").await;
+        let not_json = json_rejection("text/plain", "{}").await;
+
+        let (path, _) = path_error::<serde_json::Error>(&rejection).unwrap();
+        let (root, message) = path_error::<serde_json::Error>(&missing).unwrap();
+
+        assert_eq!(path, "address.zip");
+        assert_eq!(root, "");
+        assert!(message.starts_with("missing field `email`"));
+        assert!(path_error::<serde_json::Error>(&not_json).is_none());
+    }
+
+    #[test]
+    fn reports_query_parameters_by_their_request_name() {
+        let missing = query_rejection_problem(query_rejection("/users"));
+        let unknown = query_rejection_problem(query_rejection("/users?sortBy=age"));
+        let malformed = query_rejection_problem(query_rejection(
+            "/users?sortBy=email&perPage=many",
+        ));
+
+        assert_eq!(missing.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(missing.code(), "request.missing_field");
+        assert_eq!(field_errors(missing)["sortBy"][0]["code"], "required");
+
+        assert_eq!(unknown.code(), "request.invalid_field_type");
+        let errors = field_errors(unknown);
+        assert_eq!(errors["sortBy"][0]["code"], "invalid_type");
+        assert_eq!(errors["sortBy"][0]["params"]["expected"], "`email`");
+
+        // Numbers are parsed from text, the error doesn't name a type
+        assert_eq!(malformed.code(), "request.invalid_field_type");
+        assert_eq!(field_errors(malformed)["perPage"][0]["code"], "invalid");
+    }
+
+    #[tokio::test]
+    async fn never_echoes_the_submitted_value() {
+        let short = SignUp {
+            email: "alice@example.com".to_string(),
+            confirm_password: "hunter2".to_string(),
+            address: None,
+        };
+        let errors = short.validate().unwrap_err();
+        let body = r#"{
+            "email": "alice@example.com",
+            "confirmPassword": "Secret1!",
+            "address": "hunter2"
+        }"#;
+
+        let problems = [
+            validation_problem("request.invalid_body", &errors),
+            json_problem("application/json", body).await,
+            query_rejection_problem(query_rejection(
+                "/users?sortBy=email&perPage=hunter2",
+            )),
+        ];
+
+        for problem in problems {
+            let document = Value::Object(problem.into_document()).to_string();
+            assert!(!document.contains("hunter2"), "{document}");
+        }
+    }
+
+    async fn json_problem(content_type: &str, body: &str) -> Problem {
+        json_rejection_problem(json_rejection(content_type, body).await)
+    }
+}
@@ -0,0 +1,209 @@
+use std::{collections::HashMap, sync::OnceLock};
+
+use serde_json::{Map, Value};
+
+// Message catalogs of the API errors, embedded in the binary from
+// `locales/<tag>.toml` and keyed by error code (see `en.toml`).
+
+// |------------------------------------------------------------------|
+// |   Locale   |     Catalog     |               Example              |
+// |------------------------------------------------------------------|
+// |  en (def)  | locales/en.toml |  user.not_found = "User not found" |
+// |     es     | locales/es.toml |  user.not_found = "Usuario no..."  |
+// |------------------------------------------------------------------|
+
+// Missing keys fall back to the English catalog.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Locale {
+    En,
+    Es,
+}
+
+const CATALOGS: [(Locale, &str); 2] = [
+    (Locale::En, include_str!("../../../locales/en.toml")),
+    (Locale::Es, include_str!("../../../locales/es.toml")),
+];
+
+type Catalog = HashMap<String, String>;
+
+impl Locale {
+    pub const DEFAULT: Locale = Locale::En;
+
+    pub fn tag(self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::Es => "es",
+        }
+    }
+
+    // Only the primary subtag is considered, `es-AR` is served in `es`
+
+    fn from_tag(tag: &str) -> Option<Self> {
+        let primary = tag.split('-').next().unwrap_or_default();
+
+        match primary.trim().to_ascii_lowercase().as_str() {
+            "en" => Some(Locale::En),
+            "es" => Some(Locale::Es),
+            _ => None,
+        }
+    }
+
+    // Picks the supported language with the highest quality from an
+    // `Accept-Language` header, e.g. `es-AR,es;q=0.9,en;q=0.8`
+
+    pub fn negotiate(accept_language: Option<&str>) -> Self {
+        let Some(header) = accept_language else {
+            return Self::DEFAULT;
+        };
+
+        let mut best: Option<(Locale, f32)> = None;
+
+        for range in header.split(',') {
+            let mut parts = range.split(';');
+            let tag = parts.next().unwrap_or_default();
+
+            let quality = parts
+                .filter_map(|param| param.trim().strip_prefix("q="))
+                .find_map(|q| q.parse::<f32>().ok())
+                .unwrap_or(1.0);
+
+            let Some(locale) = Self::from_tag(tag) else {
+                continue;
+            };
+
+            if quality > 0.0 && best.is_none_or(|(_, q)| quality > q) {
+                best = Some((locale, quality));
+            }
+        }
+
+        best.map_or(Self::DEFAULT, |(locale, _)| locale)
+    }
+}
+
+// Loads and validates every catalog, so a broken one is reported when
+// the server starts instead of on the first error response.
+
+pub fn load_catalogs() {
+    catalogs();
+}
+
+// Message for `key` with its `{param}` placeholders replaced
+
+pub fn message(
+    locale: Locale,
+    key: &str,
+    params: &Map<String, Value>,
+) -> Option<String> {
+    let catalogs = catalogs();
+
+    let template = catalogs
+        .get(&locale)
+        .and_then(|catalog| catalog.get(key))
+        .or_else(|| catalogs.get(&Locale::DEFAULT)?.get(key))?;
+
+    let message = params
+        .iter()
+        .fold(template.clone(), |message, (name, value)| {
+            let value = match value {
+                Value::String(value) => value.clone(),
+                value => value.to_string(),
+            };
+
+            message.replace(&format!("{{{name}}}"), &value)
+        });
+
+    Some(message)
+}
+
+fn catalogs() -> &'static HashMap<Locale, Catalog> {
+    static CATALOGS_BY_LOCALE: OnceLock<HashMap<Locale, Catalog>> = OnceLock::new();
+
+    CATALOGS_BY_LOCALE.get_or_init(|| {
+        CATALOGS
+            .iter()
+            .map(|(locale, source)| {
+                let table =
+                    toml::from_str::<toml::Table>(source).unwrap_or_else(|e| {
+                        panic!("Invalid message catalog {}.toml: {e}", locale.tag())
+                    });
+
+                let mut catalog = Catalog::new();
+                flatten(&table, "", &mut catalog);
+
+                (*locale, catalog)
+            })
+            .collect()
+    })
+}
+
+// `[validation.length] min = "..."` becomes `validation.length.min`
+
+fn flatten(table: &toml::Table, prefix: &str, catalog: &mut Catalog) {
+    for (key, value) in table {
+        let key = match prefix {
+            "" => key.clone(),
+            prefix => format!("{prefix}.{key}"),
+        };
+
+        match value {
+            toml::Value::Table(nested) => flatten(nested, &key, catalog),
+            toml::Value::String(message) => {
+                catalog.insert(key, message.clone());
+            }
+            value => panic!("Message {key} must be a string, got {value}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use super::*;
+
+    #[test]
+    fn negotiates_the_supported_language_with_the_highest_quality() {
+        let cases = [
+            (None, Locale::En),
+            (Some("es"), Locale::Es),
+            (Some("es;q=0.9,en;q=0.8"), Locale::Es),
+            (Some("es;q=0.8,en;q=0.9"), Locale::En),
+            (Some("es;q=0.9,en"), Locale::En),
+            (Some("en,es"), Locale::En),
+            (Some("es-CL"), Locale::Es),
+            (Some("ES-cl, en;q=0.5"), Locale::Es),
+            (Some("es;q=0"), Locale::En),
+            (Some("es;q=0, en;q=0"), Locale::En),
+            (Some("*"), Locale::En),
+            (Some("*, es;q=0.5"), Locale::Es),
+            (Some("fr"), Locale::En),
+            (Some("fr, es;q=0.1"), Locale::Es),
+            (Some("es;q=abc"), Locale::Es),
+            (Some(""), Locale::En),
+        ];
+
+        for (header, expected) in cases {
+            assert_eq!(Locale::negotiate(header), expected, "{header:?}");
+        }
+    }
+
+    #[test]
+    fn every_catalog_has_the_same_keys() {
+        let catalogs = catalogs();
+        let keys = |locale| catalogs[&locale].keys().collect::<BTreeSet<_>>();
+        let default = keys(Locale::DEFAULT);
+
+        for (locale, _) in CATALOGS {
+            let keys = keys(locale);
+            let mismatched = default.symmetric_difference(&keys).collect::<Vec<_>>();
+
+            assert!(
+                mismatched.is_empty(),
+                "{}.toml and {}.toml differ in {mismatched:?}",
+                locale.tag(),
+                Locale::DEFAULT.tag()
+            );
+        }
+    }
+}
@@ -1,10 +1,41 @@
 mod database;
 mod di;
+mod health;
+pub mod i18n;
+mod mailer;
+mod shutdown;
+mod telemetry;
 mod http {
     pub mod extractors;
+    pub mod guards;
     pub mod logger;
+    pub mod metrics;
+    pub mod openapi;
+    pub mod probes;
+    pub mod problem;
+    pub mod request_id;
+    pub mod validation;
 }
 
 pub use database::*;
 pub use di::*;
+pub use health::*;
 pub use http::*;
+pub use mailer::*;
+pub use shutdown::*;
+pub use telemetry::*;
+
+#[cfg(test)]
+pub mod testing {
+    mod bounds;
+    mod database;
+    mod fixtures;
+    mod module;
+    mod transaction;
+
+    pub use bounds::*;
+    pub use database::*;
+    pub use fixtures::*;
+    pub use module::*;
+    pub use transaction::*;
+}
@@ -0,0 +1,74 @@
+use std::{sync::Mutex, time::Duration};
+
+use shaku::Component;
+
+use crate::shared::application::{ShutdownHook, ShutdownHooks};
+
+// |-------------------------------------------------------------------|
+// |  Phase  |                        Action                           |
+// |-------------------------------------------------------------------|
+// |    1    |  SIGINT / SIGTERM received, stop accepting connections  |
+// |    2    |  Drain in-flight requests (server.shutdown_timeout)     |
+// |    3    |  Run the registered hooks, last registered first        |
+// |-------------------------------------------------------------------|
+
+#[derive(Component, Default)]
+#[shaku(interface = ShutdownHooks)]
+pub struct ShutdownRegistry {
+    #[shaku(default)]
+    hooks: Mutex<Vec<(String, ShutdownHook)>>,
+}
+
+impl ShutdownHooks for ShutdownRegistry {
+    fn register(&self, name: &str, hook: ShutdownHook) {
+        if let Ok(mut hooks) = self.hooks.lock() {
+            hooks.push((name.to_string(), hook));
+        }
+    }
+
+    fn take_all(&self) -> Vec<(String, ShutdownHook)> {
+        self.hooks
+            .lock()
+            .map(|mut hooks| hooks.drain(..).rev().collect())
+            .unwrap_or_default()
+    }
+}
+
+// Resolves when the process receives SIGINT (Ctrl+C) or SIGTERM (docker stop)
+
+pub async fn shutdown_signal() -> &'static str {
+    let interrupt = async {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("Failed to listen for SIGINT");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("Failed to listen for SIGTERM")
+            .recv()
+            .await;
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = interrupt => "SIGINT",
+        _ = terminate => "SIGTERM",
+    }
+}
+
+// Runs every hook with its own time limit, a stuck hook is logged and
+// skipped so the remaining resources are still released.
+
+pub async fn run_shutdown_hooks(hooks: &dyn ShutdownHooks, timeout: Duration) {
+    for (name, hook) in hooks.take_all() {
+        tracing::info!("Running shutdown hook: {name}");
+
+        if tokio::time::timeout(timeout, hook()).await.is_err() {
+            tracing::warn!("Shutdown hook {name} timed out after {timeout:?}");
+        }
+    }
+}
@@ -0,0 +1,203 @@
+use axum::http::HeaderMap;
+use opentelemetry::{global, trace::TracerProvider, KeyValue};
+use opentelemetry_http::{HeaderExtractor, HeaderInjector};
+use opentelemetry_otlp::{SpanExporter, WithExportConfig};
+use opentelemetry_sdk::{
+    propagation::TraceContextPropagator, trace::SdkTracerProvider, Resource,
+};
+use tracing::Span;
+use tracing_opentelemetry::{OpenTelemetryLayer, OpenTelemetrySpanExt};
+use tracing_subscriber::{
+    layer::SubscriberExt, registry::LookupSpan, util::SubscriberInitExt, EnvFilter,
+    Layer,
+};
+
+use crate::shared::settings::{LoggingSettings, TelemetrySettings};
+
+// |--------------------------------------------------------------------|
+// |      Span       |        Created by         |         Example        |
+// |--------------------------------------------------------------------|
+// |  request        |  `TraceMakeSpan`          |  GET /users/{id}       |
+// |  use_case       |  `observe`                |  GetUserByIdCase       |
+// |  repository     |  `#[instrument]` on impls |  UserRepository::find  |
+// |--------------------------------------------------------------------|
+
+// The W3C trace context (`traceparent`/`tracestate` headers) of incoming
+// requests becomes the parent of the request span, so traces continue
+// across services. Spans are exported in batches over OTLP/HTTP when an
+// endpoint is configured, otherwise they only feed the logs.
+
+pub fn init_tracing(
+    logging: &LoggingSettings,
+    settings: &TelemetrySettings,
+) -> SdkTracerProvider {
+    global::set_text_map_propagator(TraceContextPropagator::new());
+
+    let resource = Resource::builder()
+        .with_service_name(settings.service_name.clone())
+        .with_attribute(KeyValue::new("service.version", env!("CARGO_PKG_VERSION")))
+        .build();
+
+    let mut provider = SdkTracerProvider::builder().with_resource(resource);
+
+    if let Some(endpoint) = settings.traces_endpoint() {
+        let exporter = SpanExporter::builder()
+            .with_http()
+            .with_endpoint(endpoint)
+            .with_timeout(settings.otlp_timeout())
+            .build()
+            .expect("Failed to build the OTLP span exporter");
+
+        provider = provider.with_batch_exporter(exporter);
+    }
+
+    let provider = provider.build();
+    let tracer = provider.tracer(settings.service_name.clone());
+
+    // Both values were validated when the settings were loaded
+    let filter = EnvFilter::new(&logging.level);
+
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(log_format(&logging.format))
+        .with(OpenTelemetryLayer::new(tracer))
+        .init();
+
+    provider
+}
+
+// The json format writes one object per line with the fields of the
+// enclosing spans (e.g. `request_id`), ready for log shippers.
+
+fn log_format<S>(format: &str) -> Box<dyn Layer<S> + Send + Sync>
+where
+    S: tracing::Subscriber + for<'span> LookupSpan<'span>,
+{
+    let layer = tracing_subscriber::fmt::layer().with_target(false);
+
+    match format {
+        "json" => layer
+            .json()
+            .with_current_span(true)
+            .with_span_list(true)
+            .boxed(),
+        "pretty" => layer.pretty().boxed(),
+        _ => layer.compact().boxed(),
+    }
+}
+
+pub fn extract_trace_context(headers: &HeaderMap, span: &Span) {
+    let parent = global::get_text_map_propagator(|propagator| {
+        propagator.extract(&HeaderExtractor(headers))
+    });
+
+    let _ = span.set_parent(parent);
+}
+
+// Writes the context of the current span, used on the responses so the
+// callers can correlate them and on any outgoing request.
+
+pub fn inject_trace_context(headers: &mut HeaderMap) {
+    let context = Span::current().context();
+
+    global::get_text_map_propagator(|propagator| {
+        propagator.inject_context(&context, &mut HeaderInjector(headers))
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use axum::{
+        body::Bytes, extract::State, http::HeaderValue, routing::post, Router,
+    };
+    use tokio::net::TcpListener;
+    use tracing::subscriber::with_default;
+
+    use super::*;
+
+    const TRACE_ID: &str = "4bf92f3577b34da6a3ce929d0e0e4736";
+    const TRACEPARENT: &str =
+        "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+
+    type Received = Arc<Mutex<Vec<Bytes>>>;
+
+    // Stands in for the collector, it keeps the body of every export
+
+    async fn otlp_receiver() -> (String, Received) {
+        let received = Received::default();
+        let router = Router::new()
+            .route(
+                "/v1/traces",
+                post(|State(received): State<Received>, body: Bytes| async move {
+                    received.lock().unwrap().push(body);
+                }),
+            )
+            .with_state(received.clone());
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, router).await });
+
+        (endpoint, received)
+    }
+
+    // The only test that installs the global subscriber, `init` can run
+    // once per process. The exporter uses a blocking HTTP client, so it is
+    // driven from a blocking thread while the receiver runs on the runtime.
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn exports_the_spans_to_the_otlp_endpoint() {
+        let (endpoint, received) = otlp_receiver().await;
+
+        tokio::task::spawn_blocking(move || {
+            let logging = LoggingSettings {
+                format: "compact".to_string(),
+                level: "server::shared::infrastructure::telemetry=info".to_string(),
+            };
+            let settings = TelemetrySettings {
+                otlp_endpoint: endpoint,
+                otlp_timeout: 5,
+                ..TelemetrySettings::default()
+            };
+
+            let provider = init_tracing(&logging, &settings);
+            tracing::info_span!("exported_span").in_scope(|| {});
+            provider.force_flush().unwrap();
+        })
+        .await
+        .unwrap();
+
+        let received = received.lock().unwrap();
+        assert_eq!(received.len(), 1);
+        assert!(received[0]
+            .windows(b"exported_span".len())
+            .any(|bytes| bytes == b"exported_span"));
+    }
+
+    #[test]
+    fn continues_the_incoming_trace_context() {
+        global::set_text_map_propagator(TraceContextPropagator::new());
+        let tracer = SdkTracerProvider::builder().build().tracer("test");
+        let subscriber =
+            tracing_subscriber::registry().with(OpenTelemetryLayer::new(tracer));
+
+        let mut incoming = HeaderMap::new();
+        incoming.insert("traceparent", HeaderValue::from_static(TRACEPARENT));
+        let mut outgoing = HeaderMap::new();
+
+        with_default(subscriber, || {
+            let span = tracing::info_span!("request");
+            extract_trace_context(&incoming, &span);
+            span.in_scope(|| inject_trace_context(&mut outgoing));
+        });
+
+        let traceparent = outgoing["traceparent"].to_str().unwrap();
+        let parts: Vec<&str> = traceparent.split('-').collect();
+
+        assert_eq!(parts[1], TRACE_ID);
+        assert_ne!(parts[2], "00f067aa0ba902b7");
+        assert_eq!(parts[3], "01");
+    }
+}
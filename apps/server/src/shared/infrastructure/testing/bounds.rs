@@ -0,0 +1,137 @@
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+use validator::Validate;
+
+use crate::shared::infrastructure::openapi::openapi;
+
+// utoipa only takes literals in `#[schema]`/`#[param]`, so the bounds of
+// the specification are copies of the `#[validate]` ones. These helpers
+// check that the copies still agree: each documented bound is probed
+// right below and above it, and the field must be accepted or rejected
+// as documented.
+
+// |---------------------------------------------------------------|
+// |      Keyword        |              Probes (accepted?)         |
+// |---------------------------------------------------------------|
+// |  minLength = n      |  n - 1 chars (no), n chars (yes)        |
+// |  maxLength = n      |  n chars (yes), n + 1 chars (no)        |
+// |  minimum = n        |  n - 1 (no), n (yes)                    |
+// |  maximum = n        |  n (yes), n + 1 (no)                    |
+// |  format = email     |  `not-an-email` (no)                    |
+// |---------------------------------------------------------------|
+
+// Documented properties of a request body schema, by their JSON name
+
+pub fn schema_properties(schema: &str) -> Vec<(String, Value)> {
+    let spec = serde_json::to_value(openapi()).expect("Invalid specification");
+
+    spec["components"]["schemas"][schema]["properties"]
+        .as_object()
+        .unwrap_or_else(|| panic!("{schema} has no documented properties"))
+        .iter()
+        .map(|(name, schema)| (name.clone(), schema.clone()))
+        .collect()
+}
+
+// Documented query parameters of a `GET` route
+
+pub fn query_parameters(path: &str) -> Vec<(String, Value)> {
+    let spec = serde_json::to_value(openapi()).expect("Invalid specification");
+
+    spec["paths"][path]["get"]["parameters"]
+        .as_array()
+        .unwrap_or_else(|| panic!("GET {path} has no documented parameters"))
+        .iter()
+        .map(|parameter| {
+            let name = parameter["name"].as_str().unwrap_or_default();
+            (name.to_string(), parameter["schema"].clone())
+        })
+        .collect()
+}
+
+// `valid` is a request where every documented field can be probed: the
+// string fields hold a prefix that satisfies their other rules (e.g.
+// `Aa1!` for a password), cut or padded to the probed length.
+
+pub fn assert_documented_bounds<T>(fields: &[(String, Value)], valid: Value)
+where
+    T: DeserializeOwned + Validate,
+{
+    for (field, schema) in fields {
+        let prefix = valid[field].as_str().unwrap_or_default();
+
+        for (value, accepted) in probes(schema, prefix) {
+            let mut request = valid.clone();
+            request[field] = value.clone();
+
+            let dto: T = serde_json::from_value(request)
+                .unwrap_or_else(|e| panic!("{field} = {value} doesn't parse: {e}"));
+
+            let rejected = dto.validate().is_err_and(|errors| {
+                let errors = errors.field_errors();
+                errors.contains_key(field.as_str())
+                    || errors.contains_key(snake_case(field).as_str())
+            });
+
+            assert_eq!(
+                rejected, !accepted,
+                "{field} = {value} doesn't match its documentation: {schema}"
+            );
+        }
+    }
+}
+
+fn probes(schema: &Value, prefix: &str) -> Vec<(Value, bool)> {
+    let bound = |keyword: &str| schema[keyword].as_f64().map(|bound| bound as u64);
+    let mut probes = Vec::new();
+
+    if let Some(min) = bound("minLength") {
+        if min > 0 {
+            probes.push((text(prefix, min - 1), false));
+        }
+        probes.push((text(prefix, min), true));
+    }
+
+    if let Some(max) = bound("maxLength") {
+        probes.push((text(prefix, max), true));
+        probes.push((text(prefix, max + 1), false));
+    }
+
+    if let Some(min) = bound("minimum") {
+        if min > 0 {
+            probes.push((json!(min - 1), false));
+        }
+        probes.push((json!(min), true));
+    }
+
+    if let Some(max) = bound("maximum") {
+        probes.push((json!(max), true));
+        probes.push((json!(max + 1), false));
+    }
+
+    if schema["format"] == "email" {
+        probes.push((json!("not-an-email"), false));
+    }
+
+    probes
+}
+
+// The padding takes two bytes per character, so a rule counting bytes
+// instead of characters is caught
+
+fn text(prefix: &str, length: u64) -> Value {
+    let length = length as usize;
+    let prefix: String = prefix.chars().take(length).collect();
+    let padding = length - prefix.chars().count();
+
+    json!(format!("{prefix}{}", "ñ".repeat(padding)))
+}
+
+fn snake_case(name: &str) -> String {
+    name.chars()
+        .flat_map(|c| match c.is_ascii_uppercase() {
+            true => vec!['_', c.to_ascii_lowercase()],
+            false => vec![c],
+        })
+        .collect()
+}
@@ -0,0 +1,124 @@
+use std::{env, future::Future, panic::resume_unwind};
+
+use sqlx::{Connection, PgConnection};
+use uuid::Uuid;
+
+use super::module::test_settings;
+use crate::app::Application;
+use crate::shared::infrastructure::AppState;
+
+// Runs a test against a real Postgres, in a database created for it:
+
+// 1. `CREATE DATABASE test_<uuid>` on the server of `TEST_DATABASE_URL`
+// 2. Builds the real `AppModule` on it (`Application::set_up_di`), which
+//    runs the migrations
+// 3. Runs the test with the resulting `AppState`
+// 4. Drops the database, even if the test panicked
+
+// The variable points to any database of a local server the user can
+// create databases on, e.g. `postgres://postgres@localhost:5432/postgres`.
+// The tests using it are marked `#[ignore]`, so `cargo test` passes on
+// machines without Postgres without reporting them as run. Run them with:
+
+// TEST_DATABASE_URL=postgres://... cargo test -- --ignored
+
+// Once asked for, a missing variable fails the test instead of skipping it.
+
+const BASE_URL_VAR: &str = "TEST_DATABASE_URL";
+
+pub async fn with_test_database<F, Fut>(test: F)
+where
+    F: FnOnce(AppState) -> Fut + Send + 'static,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    let base_url = env::var(BASE_URL_VAR).unwrap_or_else(|_| {
+        panic!("{BASE_URL_VAR} must be set to run the database tests")
+    });
+
+    let database = TestDatabase::create(&base_url).await;
+
+    let mut settings = test_settings();
+    settings.database.url = database.url();
+    settings.database.min_connections = 0;
+
+    // The module is built and the test runs in their own task, a panic
+    // (e.g. a failed migration or assertion) is caught as a `JoinError`
+    // and raised again once the database is gone
+
+    let result = tokio::spawn(async move {
+        let state = Application::set_up_di(&settings).await;
+        test(state).await;
+    })
+    .await;
+
+    database.drop().await;
+
+    if let Err(e) = result {
+        if e.is_panic() {
+            resume_unwind(e.into_panic());
+        }
+        panic!("The database test was cancelled: {e}");
+    }
+}
+
+struct TestDatabase {
+    base_url: String,
+    name: String,
+}
+
+impl TestDatabase {
+    async fn create(base_url: &str) -> Self {
+        let name = format!("test_{}", Uuid::new_v4().simple());
+
+        let mut connection = PgConnection::connect(base_url)
+            .await
+            .unwrap_or_else(|e| panic!("Failed to connect to {BASE_URL_VAR}: {e}"));
+
+        sqlx::query(&format!(r#"CREATE DATABASE "{name}""#))
+            .execute(&mut connection)
+            .await
+            .unwrap_or_else(|e| panic!("Failed to create database {name}: {e}"));
+
+        let _ = connection.close().await;
+
+        Self {
+            base_url: base_url.to_string(),
+            name,
+        }
+    }
+
+    // The base url with its database replaced, query params are kept
+
+    fn url(&self) -> String {
+        let (url, params) = match self.base_url.split_once('?') {
+            Some((url, params)) => (url, format!("?{params}")),
+            None => (self.base_url.as_str(), String::new()),
+        };
+
+        let (scheme, rest) = url.split_once("://").unwrap_or(("postgres", url));
+        let server = rest.split('/').next().unwrap_or_default();
+
+        format!("{scheme}://{server}/{}{params}", self.name)
+    }
+
+    // `WITH (FORCE)` closes the connections the test may have leaked
+
+    async fn drop(self) {
+        let result = async {
+            let mut connection = PgConnection::connect(&self.base_url).await?;
+
+            sqlx::query(&format!(
+                r#"DROP DATABASE IF EXISTS "{}" WITH (FORCE)"#,
+                self.name
+            ))
+            .execute(&mut connection)
+            .await?;
+
+            connection.close().await
+        };
+
+        if let Err(e) = result.await {
+            eprintln!("Failed to drop the test database {}: {e}", self.name);
+        }
+    }
+}
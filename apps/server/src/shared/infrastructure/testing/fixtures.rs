@@ -0,0 +1,24 @@
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::features::user::domain::User;
+
+// An unverified user with a fake password hash, tests change the other
+// fields with the struct update syntax:
+
+// User { validated: true, ..user("alice", "alice@example.com") }
+
+pub fn user(username: &str, email: &str) -> User {
+    let now = Utc::now();
+
+    User {
+        id: Uuid::new_v4(),
+        username: username.to_string(),
+        email: email.to_string(),
+        password: "hash".to_string(),
+        validated: false,
+        created_at: now,
+        updated_at: now,
+        deleted_at: None,
+    }
+}
@@ -0,0 +1,200 @@
+use std::{sync::Arc, time::Duration};
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Method, Request, StatusCode},
+    middleware, Router,
+};
+use serde_json::Value;
+use sqlx::postgres::PgPoolOptions;
+use tower::ServiceExt;
+
+use crate::app::Application;
+use crate::features::{
+    auth::{
+        domain::{
+            AuthUnitOfWork, PasswordResetTokenRepository, RefreshTokenRepository,
+        },
+        infrastructure::InMemoryAuthUnitOfWork,
+    },
+    role::{domain::RoleRepository, infrastructure::InMemoryRoleRepository},
+    user::{
+        application::services::{FakePasswordHasher, PasswordHasher},
+        domain::{UserRepository, UserUnitOfWork, VerificationTokenRepository},
+        infrastructure::{InMemoryUserRepository, InMemoryUserUnitOfWork},
+    },
+};
+use crate::shared::{
+    application::Mailer,
+    infrastructure::{
+        database::PostgresDatabase, request_id::request_id, AppModule, AppState,
+        InMemoryMailer,
+    },
+    settings::{AppSettings, Settings},
+};
+
+// Builds the `AppModule` for tests, the components that need a database or
+// send emails are replaced so the application runs fully in memory:
+
+// |-------------------------------------------------------------------|
+// |             Interface             |         Test component        |
+// |-------------------------------------------------------------------|
+// |  UserUnitOfWork                   |  InMemoryUserUnitOfWork       |
+// |  AuthUnitOfWork                   |  InMemoryAuthUnitOfWork       |
+// |  UserRepository                   |  InMemoryUserRepository       |
+// |  VerificationTokenRepository      |  InMemoryVerificationToken... |
+// |  RefreshTokenRepository           |  InMemoryRefreshToken...      |
+// |  PasswordResetTokenRepository     |  InMemoryPasswordResetToken...|
+// |  RoleRepository                   |  InMemoryRoleRepository       |
+// |  PasswordHasher                   |  FakePasswordHasher           |
+// |  Mailer                           |  InMemoryMailer               |
+// |  DatabaseConnection               |  Lazy pool, never connected   |
+// |-------------------------------------------------------------------|
+
+pub struct TestModuleBuilder {
+    settings: Settings,
+}
+
+pub struct TestApp {
+    pub state: AppState,
+    pub users: InMemoryUserRepository,
+    pub hasher: FakePasswordHasher,
+    pub mailer: InMemoryMailer,
+}
+
+// Valid settings for tests, emails are kept in memory
+
+pub fn test_settings() -> Settings {
+    let mut settings = Settings::default();
+
+    settings.database.url = "postgres://localhost/unused".to_string();
+    settings.auth.access_secret =
+        "test-access-secret-of-thirty-two-bytes".to_string();
+    settings.auth.refresh_secret =
+        "test-refresh-secret-of-thirty-two-bytes".to_string();
+    settings.public.api_url = "http://api.test".to_string();
+    settings.public.web_url = "http://web.test".to_string();
+    settings.mail.backend = "memory".to_string();
+    settings.mail.from = "no-reply@test.dev".to_string();
+
+    settings
+}
+
+impl TestModuleBuilder {
+    pub fn new() -> Self {
+        Self {
+            settings: test_settings(),
+        }
+    }
+
+    pub fn settings(mut self, configure: impl FnOnce(&mut Settings)) -> Self {
+        configure(&mut self.settings);
+        self
+    }
+
+    // Must be called inside a Tokio runtime, the pool spawns its tasks
+
+    pub fn build(self) -> TestApp {
+        let pool = PgPoolOptions::new()
+            .acquire_timeout(Duration::from_millis(100))
+            .connect_lazy(&self.settings.database.url)
+            .expect("Invalid test database url");
+
+        let user_unit_of_work = InMemoryUserUnitOfWork::default();
+        let users = user_unit_of_work.users.clone();
+        let auth_unit_of_work = InMemoryAuthUnitOfWork {
+            users: users.clone(),
+            ..InMemoryAuthUnitOfWork::default()
+        };
+        let hasher = FakePasswordHasher::default();
+        let mailer = InMemoryMailer::default();
+
+        let module = AppModule::builder()
+            .with_component_parameters::<AppSettings>(self.settings.into())
+            .with_component_parameters::<PostgresDatabase>(
+                PostgresDatabase { pool }.into(),
+            )
+            .with_component_override::<dyn UserRepository>(Box::new(users.clone()))
+            .with_component_override::<dyn VerificationTokenRepository>(Box::new(
+                user_unit_of_work.verification_tokens.clone(),
+            ))
+            .with_component_override::<dyn UserUnitOfWork>(Box::new(
+                user_unit_of_work,
+            ))
+            .with_component_override::<dyn RefreshTokenRepository>(Box::new(
+                auth_unit_of_work.refresh_tokens.clone(),
+            ))
+            .with_component_override::<dyn PasswordResetTokenRepository>(Box::new(
+                auth_unit_of_work.password_reset_tokens.clone(),
+            ))
+            .with_component_override::<dyn AuthUnitOfWork>(Box::new(
+                auth_unit_of_work,
+            ))
+            .with_component_override::<dyn RoleRepository>(Box::new(
+                InMemoryRoleRepository::default(),
+            ))
+            .with_component_override::<dyn PasswordHasher>(Box::new(hasher.clone()))
+            .with_component_override::<dyn Mailer>(Box::new(mailer.clone()))
+            .build();
+
+        TestApp {
+            state: AppState {
+                module: Arc::new(module),
+            },
+            users,
+            hasher,
+            mailer,
+        }
+    }
+}
+
+impl TestApp {
+    pub fn module(&self) -> &AppModule {
+        &self.state.module
+    }
+
+    // Same routes and error handling as the server, without the tracing
+    // and CORS layers
+
+    pub fn router(&self) -> Router {
+        Application::routes(self.state.clone())
+            .layer(middleware::from_fn(request_id))
+    }
+
+    // Sends the request through `oneshot`, the body is parsed as JSON
+    // (`Value::Null` when it is empty or not JSON)
+
+    pub async fn send(&self, request: Request<Body>) -> (StatusCode, Value) {
+        let response = self
+            .router()
+            .oneshot(request)
+            .await
+            .expect("The router is infallible");
+
+        let status = response.status();
+        let bytes = to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("Failed to read the response body");
+
+        (
+            status,
+            serde_json::from_slice(&bytes).unwrap_or(Value::Null),
+        )
+    }
+}
+
+pub fn json_request(method: Method, uri: &str, body: Value) -> Request<Body> {
+    Request::builder()
+        .method(method)
+        .uri(uri)
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(body.to_string()))
+        .expect("Invalid test request")
+}
+
+pub fn get_request(uri: &str) -> Request<Body> {
+    Request::builder()
+        .uri(uri)
+        .body(Body::empty())
+        .expect("Invalid test request")
+}
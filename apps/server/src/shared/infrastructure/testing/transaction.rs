@@ -0,0 +1,30 @@
+// Rollback of the in-memory units of work. Their repositories apply the
+// writes right away, when a transaction begins each one registers how to
+// restore its current contents. A rollback (or dropping the log without
+// committing) restores them in reverse order. There is no isolation,
+// other requests see the writes before the commit.
+
+type Undo = Box<dyn FnOnce() + Send + Sync>;
+
+#[derive(Default)]
+pub struct RollbackLog {
+    undo: Vec<Undo>,
+}
+
+impl RollbackLog {
+    pub fn on_rollback(&mut self, undo: impl FnOnce() + Send + Sync + 'static) {
+        self.undo.push(Box::new(undo));
+    }
+
+    pub fn commit(mut self) {
+        self.undo.clear();
+    }
+}
+
+impl Drop for RollbackLog {
+    fn drop(&mut self) {
+        while let Some(undo) = self.undo.pop() {
+            undo();
+        }
+    }
+}
@@ -0,0 +1,573 @@
+use std::{env, fmt, fs, io::ErrorKind, str::FromStr, time::Duration};
+
+use axum::http::{HeaderName, Method};
+use serde::Deserialize;
+use shaku::{Component, Interface};
+
+// Typed configuration of the application, resolved once at startup and
+// registered in the `AppModule`, components receive it with
+// `#[shaku(inject)] settings: Arc<dyn SettingsProvider>`.
+
+// |-------------------------------------------------------------------|
+// |  Priority  |           Source           |          Example         |
+// |-------------------------------------------------------------------|
+// |   1 (top)  |   Environment variables    |  JWT_ACCESS_TOKEN_TTL    |
+// |     2      |  TOML file (SETTINGS_FILE) |  [auth] access_token_ttl |
+// |     3      |    Defaults (`Default`)    |  900                     |
+// |-------------------------------------------------------------------|
+
+// The file defaults to `config/settings.toml` and is optional, secrets
+// are expected to come from the environment. Every problem found while
+// loading is collected, so a broken deployment reports all of them at once.
+
+const DEFAULT_SETTINGS_FILE: &str = "config/settings.toml";
+const MIN_SECRET_LENGTH: usize = 32;
+
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct Settings {
+    pub server: ServerSettings,
+    pub database: DatabaseSettings,
+    pub auth: AuthSettings,
+    pub public: PublicSettings,
+    pub mail: MailSettings,
+    pub cors: CorsSettings,
+    pub users: UsersSettings,
+    pub logging: LoggingSettings,
+    pub telemetry: TelemetrySettings,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServerSettings {
+    pub host: String,
+    pub port: u16,
+    // Seconds to wait for in-flight requests (and each shutdown hook)
+    pub shutdown_timeout: u64,
+}
+
+impl Default for ServerSettings {
+    fn default() -> Self {
+        Self {
+            host: "0.0.0.0".to_string(),
+            port: 8000,
+            shutdown_timeout: 30,
+        }
+    }
+}
+
+impl ServerSettings {
+    pub fn address(&self) -> String {
+        format!("{}:{}", self.host, self.port)
+    }
+
+    pub fn shutdown_timeout(&self) -> Duration {
+        Duration::from_secs(self.shutdown_timeout)
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct DatabaseSettings {
+    pub url: String,
+    pub min_connections: u32,
+    pub max_connections: u32,
+    // Seconds to wait for a free connection of the pool
+    pub acquire_timeout: u64,
+}
+
+impl Default for DatabaseSettings {
+    fn default() -> Self {
+        Self {
+            url: String::new(),
+            min_connections: 1,
+            max_connections: 10,
+            acquire_timeout: 5,
+        }
+    }
+}
+
+// Every lifetime is expressed in seconds
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct AuthSettings {
+    pub access_secret: String,
+    pub refresh_secret: String,
+    pub access_token_ttl: i64,
+    pub refresh_token_ttl: i64,
+    pub email_verification_token_ttl: i64,
+    pub password_reset_token_ttl: i64,
+    pub password_hash_cost: u32,
+}
+
+impl Default for AuthSettings {
+    fn default() -> Self {
+        Self {
+            access_secret: String::new(),
+            refresh_secret: String::new(),
+            access_token_ttl: 15 * 60,
+            refresh_token_ttl: 7 * 24 * 60 * 60,
+            email_verification_token_ttl: 24 * 60 * 60,
+            password_reset_token_ttl: 60 * 60,
+            password_hash_cost: 10,
+        }
+    }
+}
+
+// Public URLs used to build the links sent by email
+
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct PublicSettings {
+    pub api_url: String,
+    pub web_url: String,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct MailSettings {
+    // One of: smtp | file | memory
+    pub backend: String,
+    pub from: String,
+    pub drop_dir: String,
+    // Only required by the smtp backend
+    pub smtp_url: String,
+}
+
+impl Default for MailSettings {
+    fn default() -> Self {
+        Self {
+            backend: "file".to_string(),
+            from: String::new(),
+            drop_dir: "./mails".to_string(),
+            smtp_url: String::new(),
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct CorsSettings {
+    pub allowed_methods: Vec<String>,
+    pub allowed_headers: Vec<String>,
+}
+
+impl Default for CorsSettings {
+    fn default() -> Self {
+        Self {
+            allowed_methods: ["GET", "POST", "PUT", "DELETE", "PATCH"]
+                .map(String::from)
+                .to_vec(),
+            allowed_headers: ["content-type", "authorization"]
+                .map(String::from)
+                .to_vec(),
+        }
+    }
+}
+
+impl CorsSettings {
+    // Both lists are validated when the settings are loaded
+
+    pub fn methods(&self) -> Vec<Method> {
+        self.allowed_methods
+            .iter()
+            .filter_map(|method| Method::from_str(method).ok())
+            .collect()
+    }
+
+    pub fn headers(&self) -> Vec<HeaderName> {
+        self.allowed_headers
+            .iter()
+            .filter_map(|header| HeaderName::from_str(header).ok())
+            .collect()
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct UsersSettings {
+    // Soft-deleted users are purged after this many seconds
+    pub retention_period: i64,
+    // How often the retention job runs, in seconds
+    pub purge_interval: i64,
+}
+
+impl Default for UsersSettings {
+    fn default() -> Self {
+        Self {
+            retention_period: 30 * 24 * 60 * 60,
+            purge_interval: 60 * 60,
+        }
+    }
+}
+
+// `level` takes `EnvFilter` directives, e.g. `info,sqlx=warn,server=debug`
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct LoggingSettings {
+    // One of: pretty | compact | json
+    pub format: String,
+    pub level: String,
+}
+
+impl Default for LoggingSettings {
+    fn default() -> Self {
+        Self {
+            format: "compact".to_string(),
+            level: "info".to_string(),
+        }
+    }
+}
+
+// Spans are always created and propagated (`traceparent`), they are only
+// exported when an OTLP/HTTP collector endpoint is configured, e.g.
+// `http://localhost:4318` (`/v1/traces` is appended).
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct TelemetrySettings {
+    pub service_name: String,
+    pub otlp_endpoint: String,
+    // Seconds to wait for the collector on each export
+    pub otlp_timeout: u64,
+}
+
+impl Default for TelemetrySettings {
+    fn default() -> Self {
+        Self {
+            service_name: "server".to_string(),
+            otlp_endpoint: String::new(),
+            otlp_timeout: 10,
+        }
+    }
+}
+
+impl TelemetrySettings {
+    pub fn traces_endpoint(&self) -> Option<String> {
+        if self.otlp_endpoint.is_empty() {
+            return None;
+        }
+
+        Some(format!(
+            "{}/v1/traces",
+            self.otlp_endpoint.trim_end_matches('/')
+        ))
+    }
+
+    pub fn otlp_timeout(&self) -> Duration {
+        Duration::from_secs(self.otlp_timeout)
+    }
+}
+
+#[derive(Debug)]
+pub struct SettingsError(pub Vec<String>);
+
+impl fmt::Display for SettingsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for error in &self.0 {
+            writeln!(f, "  - {error}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for SettingsError {}
+
+pub trait SettingsProvider: Interface {
+    fn get(&self) -> &Settings;
+}
+
+#[derive(Component)]
+#[shaku(interface = SettingsProvider)]
+pub struct AppSettings {
+    settings: Settings,
+}
+
+impl SettingsProvider for AppSettings {
+    fn get(&self) -> &Settings {
+        &self.settings
+    }
+}
+
+impl From<Settings> for AppSettingsParameters {
+    fn from(settings: Settings) -> Self {
+        AppSettingsParameters { settings }
+    }
+}
+
+impl Settings {
+    pub fn load() -> Result<Self, SettingsError> {
+        let mut errors = Vec::new();
+
+        let path = env::var("SETTINGS_FILE")
+            .unwrap_or_else(|_| DEFAULT_SETTINGS_FILE.to_string());
+
+        let mut settings = match fs::read_to_string(&path) {
+            Ok(content) => toml::from_str(&content).unwrap_or_else(|e| {
+                errors.push(format!("{path}: {e}"));
+                Settings::default()
+            }),
+            Err(e) if e.kind() == ErrorKind::NotFound => Settings::default(),
+            Err(e) => {
+                errors.push(format!("{path}: {e}"));
+                Settings::default()
+            }
+        };
+
+        settings.apply_env(&mut EnvOverrides {
+            errors: &mut errors,
+        });
+        settings.validate(&mut errors);
+
+        match errors.is_empty() {
+            true => Ok(settings),
+            false => Err(SettingsError(errors)),
+        }
+    }
+
+    // The variable names are the ones documented in `.env.example`
+
+    fn apply_env(&mut self, env: &mut EnvOverrides) {
+        env.set("SERVER_HOST", &mut self.server.host);
+        env.set("SERVER_PORT", &mut self.server.port);
+        env.set("SERVER_SHUTDOWN_TIMEOUT", &mut self.server.shutdown_timeout);
+
+        env.set("POSTGRES_DATABASE_URL", &mut self.database.url);
+        env.set(
+            "DATABASE_MIN_CONNECTIONS",
+            &mut self.database.min_connections,
+        );
+        env.set(
+            "DATABASE_MAX_CONNECTIONS",
+            &mut self.database.max_connections,
+        );
+        env.set(
+            "DATABASE_ACQUIRE_TIMEOUT",
+            &mut self.database.acquire_timeout,
+        );
+
+        env.set("JWT_ACCESS_SECRET", &mut self.auth.access_secret);
+        env.set("JWT_REFRESH_SECRET", &mut self.auth.refresh_secret);
+        env.set("JWT_ACCESS_TOKEN_TTL", &mut self.auth.access_token_ttl);
+        env.set("JWT_REFRESH_TOKEN_TTL", &mut self.auth.refresh_token_ttl);
+        env.set(
+            "EMAIL_VERIFICATION_TOKEN_TTL",
+            &mut self.auth.email_verification_token_ttl,
+        );
+        env.set(
+            "PASSWORD_RESET_TOKEN_TTL",
+            &mut self.auth.password_reset_token_ttl,
+        );
+        env.set("PASSWORD_HASH_COST", &mut self.auth.password_hash_cost);
+
+        env.set("PUBLIC_API_URL", &mut self.public.api_url);
+        env.set("PUBLIC_WEB_URL", &mut self.public.web_url);
+
+        env.set("MAILER_BACKEND", &mut self.mail.backend);
+        env.set("MAIL_FROM", &mut self.mail.from);
+        env.set("MAIL_DROP_DIR", &mut self.mail.drop_dir);
+        env.set("SMTP_URL", &mut self.mail.smtp_url);
+
+        env.set_list("CORS_ALLOWED_METHODS", &mut self.cors.allowed_methods);
+        env.set_list("CORS_ALLOWED_HEADERS", &mut self.cors.allowed_headers);
+
+        env.set("USER_RETENTION_PERIOD", &mut self.users.retention_period);
+        env.set("USER_PURGE_INTERVAL", &mut self.users.purge_interval);
+
+        env.set("LOG_FORMAT", &mut self.logging.format);
+        env.set("RUST_LOG", &mut self.logging.level);
+
+        env.set("OTEL_SERVICE_NAME", &mut self.telemetry.service_name);
+        env.set(
+            "OTEL_EXPORTER_OTLP_ENDPOINT",
+            &mut self.telemetry.otlp_endpoint,
+        );
+        env.set(
+            "OTEL_EXPORTER_OTLP_TIMEOUT",
+            &mut self.telemetry.otlp_timeout,
+        );
+    }
+
+    fn validate(&self, errors: &mut Vec<String>) {
+        let mut required = |value: &str, name: &str| {
+            if value.trim().is_empty() {
+                errors.push(format!("{name} is required"));
+            }
+        };
+
+        required(&self.database.url, "database.url (POSTGRES_DATABASE_URL)");
+        required(
+            &self.auth.access_secret,
+            "auth.access_secret (JWT_ACCESS_SECRET)",
+        );
+        required(
+            &self.auth.refresh_secret,
+            "auth.refresh_secret (JWT_REFRESH_SECRET)",
+        );
+        required(&self.public.api_url, "public.api_url (PUBLIC_API_URL)");
+        required(&self.public.web_url, "public.web_url (PUBLIC_WEB_URL)");
+        required(&self.mail.from, "mail.from (MAIL_FROM)");
+
+        if self.mail.backend == "smtp" {
+            required(&self.mail.smtp_url, "mail.smtp_url (SMTP_URL)");
+        }
+
+        // The tokens are signed with HMAC-SHA256, a key shorter than the
+        // hash is easier to brute force than the signature
+
+        let secrets = [
+            (&self.auth.access_secret, "auth.access_secret"),
+            (&self.auth.refresh_secret, "auth.refresh_secret"),
+        ];
+
+        for (secret, name) in secrets {
+            if !secret.is_empty() && secret.len() < MIN_SECRET_LENGTH {
+                errors.push(format!(
+                    "{name} must be at least {MIN_SECRET_LENGTH} bytes long"
+                ));
+            }
+        }
+
+        if !self.auth.access_secret.is_empty()
+            && self.auth.access_secret == self.auth.refresh_secret
+        {
+            errors.push(
+                "auth.access_secret and auth.refresh_secret must be different"
+                    .into(),
+            );
+        }
+
+        let periods = [
+            (self.auth.access_token_ttl, "auth.access_token_ttl"),
+            (self.auth.refresh_token_ttl, "auth.refresh_token_ttl"),
+            (
+                self.auth.email_verification_token_ttl,
+                "auth.email_verification_token_ttl",
+            ),
+            (
+                self.auth.password_reset_token_ttl,
+                "auth.password_reset_token_ttl",
+            ),
+            (self.users.retention_period, "users.retention_period"),
+            (self.users.purge_interval, "users.purge_interval"),
+        ];
+
+        for (seconds, name) in periods {
+            if seconds <= 0 {
+                errors.push(format!("{name} must be a positive number of seconds"));
+            }
+        }
+
+        if self.database.max_connections == 0
+            || self.database.min_connections > self.database.max_connections
+        {
+            errors.push(
+                "database.min_connections must not exceed a non-zero \
+                 database.max_connections"
+                    .to_string(),
+            );
+        }
+
+        // bcrypt only accepts costs between 4 and 31
+
+        if !(4..=31).contains(&self.auth.password_hash_cost) {
+            errors.push("auth.password_hash_cost must be between 4 and 31".into());
+        }
+
+        if !["smtp", "file", "memory"].contains(&self.mail.backend.as_str()) {
+            errors.push(format!(
+                "mail.backend must be one of smtp, file or memory, got {:?}",
+                self.mail.backend
+            ));
+        }
+
+        if !self.mail.from.is_empty()
+            && self.mail.from.parse::<lettre::message::Mailbox>().is_err()
+        {
+            errors.push(format!(
+                "mail.from is not a valid address: {:?}",
+                self.mail.from
+            ));
+        }
+
+        for method in &self.cors.allowed_methods {
+            if Method::from_str(method).is_err() {
+                errors.push(format!(
+                    "cors.allowed_methods: invalid method {method:?}"
+                ));
+            }
+        }
+
+        for header in &self.cors.allowed_headers {
+            if HeaderName::from_str(header).is_err() {
+                errors.push(format!(
+                    "cors.allowed_headers: invalid header {header:?}"
+                ));
+            }
+        }
+
+        if !["pretty", "compact", "json"].contains(&self.logging.format.as_str()) {
+            errors.push(format!(
+                "logging.format must be one of pretty, compact or json, got {:?}",
+                self.logging.format
+            ));
+        }
+
+        if let Err(e) = tracing_subscriber::EnvFilter::try_new(&self.logging.level) {
+            errors.push(format!("logging.level: {e}"));
+        }
+
+        if self.telemetry.service_name.is_empty() {
+            errors.push("telemetry.service_name is required".into());
+        }
+
+        if let Some(endpoint) = self.telemetry.traces_endpoint() {
+            let valid = endpoint
+                .parse::<axum::http::Uri>()
+                .is_ok_and(|uri| matches!(uri.scheme_str(), Some("http" | "https")));
+
+            if !valid {
+                errors.push(format!(
+                    "telemetry.otlp_endpoint is not a valid http(s) url: {:?}",
+                    self.telemetry.otlp_endpoint
+                ));
+            }
+        }
+    }
+}
+
+// Applies the environment variables over the values read from the file,
+// values that can't be parsed are reported instead of being ignored.
+
+struct EnvOverrides<'a> {
+    errors: &'a mut Vec<String>,
+}
+
+impl EnvOverrides<'_> {
+    fn set<T: FromStr>(&mut self, key: &str, target: &mut T) {
+        let Ok(raw) = env::var(key) else {
+            return;
+        };
+
+        match raw.parse() {
+            Ok(value) => *target = value,
+            Err(_) => self.errors.push(format!("{key}: invalid value {raw:?}")),
+        }
+    }
+
+    // Comma separated lists, e.g. `GET,POST`
+
+    fn set_list(&mut self, key: &str, target: &mut Vec<String>) {
+        if let Ok(raw) = env::var(key) {
+            *target = raw
+                .split(',')
+                .map(str::trim)
+                .filter(|item| !item.is_empty())
+                .map(String::from)
+                .collect();
+        }
+    }
+}